@@ -0,0 +1,426 @@
+use std::io::{Read, Seek};
+
+use crate::dbf::{DbfReader, Encoding, FieldInfo, FieldType, FieldValue};
+use crate::error::{BatchSizeError, FieldParseError, NoSuchFieldError};
+use crate::model::{Currency, Date, Timestamp};
+
+#[derive(Debug, Clone)]
+pub enum Column {
+    Logical(Vec<Option<bool>>),
+    Character(Vec<Option<String>>),
+    Integer(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Decimal(Vec<Option<i128>>),
+    Currency(Vec<Option<Currency>>),
+    Date(Vec<Option<Date>>),
+    Timestamp(Vec<Option<Timestamp>>),
+    Bytes(Vec<Option<Vec<u8>>>),
+}
+
+impl Column {
+    pub fn with_capacity(field: &FieldInfo, capacity: usize) -> Self {
+        match field.field_type {
+            FieldType::Logical => Column::Logical(Vec::with_capacity(capacity)),
+            FieldType::Character | FieldType::Varchar | FieldType::Memo => {
+                Column::Character(Vec::with_capacity(capacity))
+            }
+            FieldType::Integer => Column::Integer(Vec::with_capacity(capacity)),
+            FieldType::Numeric if field.is_integral() => {
+                Column::Integer(Vec::with_capacity(capacity))
+            }
+            // including integers too long for an i64
+            FieldType::Numeric => Column::Decimal(Vec::with_capacity(capacity)),
+            FieldType::Float | FieldType::Double => Column::Float(Vec::with_capacity(capacity)),
+            FieldType::Currency => Column::Currency(Vec::with_capacity(capacity)),
+            FieldType::Date => Column::Date(Vec::with_capacity(capacity)),
+            FieldType::Timestamp => Column::Timestamp(Vec::with_capacity(capacity)),
+            FieldType::Binary | FieldType::General | FieldType::Varbinary => {
+                Column::Bytes(Vec::with_capacity(capacity))
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Logical(values) => values.len(),
+            Column::Character(values) => values.len(),
+            Column::Integer(values) => values.len(),
+            Column::Float(values) => values.len(),
+            Column::Decimal(values) => values.len(),
+            Column::Currency(values) => values.len(),
+            Column::Date(values) => values.len(),
+            Column::Timestamp(values) => values.len(),
+            Column::Bytes(values) => values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // values of another type than the column are handed back
+    fn push(&mut self, value: FieldValue) -> Result<(), FieldValue> {
+        match (self, value) {
+            (Column::Logical(values), FieldValue::Logical(val)) => values.push(Some(val)),
+            (Column::Character(values), FieldValue::Character(val))
+            | (Column::Character(values), FieldValue::Memo(val)) => values.push(Some(val)),
+            (Column::Integer(values), FieldValue::Integer(val)) => values.push(Some(val)),
+            (Column::Float(values), FieldValue::Numeric(val))
+            | (Column::Float(values), FieldValue::Float(val)) => values.push(Some(val)),
+            (Column::Currency(values), FieldValue::Currency(val)) => {
+                values.push(Some(Currency::new(val)))
+            }
+            (Column::Date(values), FieldValue::Date(year, month, day)) => {
+                values.push(Some(Date::new(year, month, day)))
+            }
+            (
+                Column::Timestamp(values),
+                FieldValue::Timestamp(year, month, day, hour, minute, second, millisecond),
+            ) => values.push(Some(Timestamp::new(
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                second,
+                millisecond,
+            ))),
+            (Column::Bytes(values), FieldValue::Binary(val))
+            | (Column::Bytes(values), FieldValue::General(val)) => values.push(Some(val)),
+            (column, FieldValue::Null) => column.push_null(),
+            (_, value) => return Err(value),
+        }
+
+        Ok(())
+    }
+
+    fn push_null(&mut self) {
+        match self {
+            Column::Logical(values) => values.push(None),
+            Column::Character(values) => values.push(None),
+            Column::Integer(values) => values.push(None),
+            Column::Float(values) => values.push(None),
+            Column::Decimal(values) => values.push(None),
+            Column::Currency(values) => values.push(None),
+            Column::Date(values) => values.push(None),
+            Column::Timestamp(values) => values.push(None),
+            Column::Bytes(values) => values.push(None),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Batch {
+    fields: Vec<FieldInfo>,
+    columns: Vec<Column>,
+    num_rows: usize,
+}
+
+impl Batch {
+    pub fn empty(fields: Vec<FieldInfo>) -> Self {
+        let columns = fields.iter().map(|f| Column::with_capacity(f, 0)).collect();
+
+        Self {
+            fields,
+            columns,
+            num_rows: 0,
+        }
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    pub fn column(&self, index: usize) -> Option<&Column> {
+        self.columns.get(index)
+    }
+
+    pub fn column_by_name(&self, name: &str) -> Option<&Column> {
+        self.fields
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(name))
+            .map(|i| &self.columns[i])
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    pub fn into_columns(self) -> Vec<Column> {
+        self.columns
+    }
+}
+
+pub struct BatchIterator<R>
+where
+    R: Read + Seek,
+{
+    reader: DbfReader<R>,
+    batch_size: usize,
+    projection: Vec<usize>,
+}
+
+impl<R: Read + Seek> BatchIterator<R> {
+    pub fn new(reader: DbfReader<R>, batch_size: usize) -> Result<Self, BatchSizeError> {
+        if batch_size == 0 {
+            return Err(BatchSizeError);
+        }
+
+        let projection = (0..reader.fields().len()).collect();
+        Ok(Self {
+            reader,
+            batch_size,
+            projection,
+        })
+    }
+
+    pub fn fields(&self) -> Vec<FieldInfo> {
+        let fields = self.reader.fields();
+        self.projection.iter().map(|i| fields[*i].clone()).collect()
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.reader.set_encoding(encoding);
+    }
+
+    pub fn set_projection<S: AsRef<str>>(&mut self, columns: &[S]) -> Result<(), NoSuchFieldError> {
+        let fields = self.reader.fields();
+        self.projection = columns
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                fields
+                    .iter()
+                    .position(|f| f.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| NoSuchFieldError::new(name))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>, Box<dyn std::error::Error>> {
+        let fields = self.fields();
+        let mut columns: Vec<Column> = fields
+            .iter()
+            .map(|f| Column::with_capacity(f, self.batch_size))
+            .collect();
+
+        let mut num_rows = 0;
+        while num_rows < self.batch_size {
+            let mut record = match self.reader.next_raw_record()? {
+                Some(record) => record,
+                None => break,
+            };
+
+            for ((i, field), column) in self.projection.iter().zip(&fields).zip(columns.iter_mut())
+            {
+                if let Column::Decimal(values) = column {
+                    values.push(record.parse_decimal(*i)?);
+                    continue;
+                }
+
+                column.push(record.parse_field(*i)?).map_err(|value| {
+                    let message = format!("unexpected value {:?}", value);
+                    FieldParseError::new(
+                        field.name.clone(),
+                        field.field_type.clone(),
+                        Some(message.into()),
+                    )
+                })?;
+            }
+
+            num_rows += 1;
+        }
+
+        if num_rows == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(Batch {
+            fields,
+            columns,
+            num_rows,
+        }))
+    }
+}
+
+impl<R: Read + Seek> Iterator for BatchIterator<R> {
+    type Item = Result<Batch, Box<dyn std::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_batch().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfWriter, Version};
+    use crate::value::Value;
+
+    fn table(
+        version: Version,
+        fields: Vec<FieldInfo>,
+        records: &[Vec<Value>],
+    ) -> DbfReader<Cursor<Vec<u8>>> {
+        let mut writer = DbfWriter::new(Cursor::new(Vec::new()), version, 0, fields).unwrap();
+        for values in records {
+            writer.write_record(values).unwrap();
+        }
+        let table = writer.finish().unwrap().into_inner();
+        DbfReader::from_reader(Cursor::new(table), None).unwrap()
+    }
+
+    #[test]
+    fn columns_by_field_type() {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 5, 0),
+            FieldInfo::with_type("PRICE", FieldType::Numeric, 8, 2),
+            FieldInfo::with_type("RATIO", FieldType::Float, 10, 3),
+            FieldInfo::with_type("ACTIVE", FieldType::Logical, 1, 0),
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+            FieldInfo::with_type("COUNT", FieldType::Integer, 4, 0),
+            FieldInfo::with_type("AMOUNT", FieldType::Currency, 8, 4),
+            FieldInfo::with_type("WEIGHT", FieldType::Double, 8, 0),
+            FieldInfo::with_type("UPDATED", FieldType::Timestamp, 8, 0),
+        ];
+        let records = vec![
+            vec![
+                Value::Str("widget".to_owned()),
+                Value::Int(42),
+                Value::Float(12.5),
+                Value::Float(0.125),
+                Value::Bool(true),
+                Value::Date(Date::new(2024, 2, 29)),
+                Value::Int(-7),
+                Value::Currency("1234.5678".parse().unwrap()),
+                Value::Float(0.25),
+                Value::Timestamp(Timestamp::new(2024, 2, 29, 13, 45, 30, 250)),
+            ],
+            vec![
+                Value::Str(String::new()),
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Null,
+                Value::Int(0),
+                Value::Currency(Currency::new(0)),
+                Value::Float(0.0),
+                Value::Null,
+            ],
+        ];
+        let reader = table(Version::VisualFoxPro, fields, &records);
+
+        let batch = BatchIterator::new(reader, 10)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert!(
+            matches!(batch.column_by_name("name"), Some(Column::Character(v))
+            if v == &[Some("widget".to_owned()), Some(String::new())])
+        );
+        assert!(
+            matches!(batch.column_by_name("qty"), Some(Column::Integer(v))
+            if v == &[Some(42), None])
+        );
+        assert!(
+            matches!(batch.column_by_name("price"), Some(Column::Decimal(v))
+            if v == &[Some(1250), None])
+        );
+        assert!(
+            matches!(batch.column_by_name("ratio"), Some(Column::Float(v))
+            if v == &[Some(0.125), None])
+        );
+        assert!(
+            matches!(batch.column_by_name("active"), Some(Column::Logical(v))
+            if v == &[Some(true), None])
+        );
+        assert!(
+            matches!(batch.column_by_name("since"), Some(Column::Date(v))
+            if v == &[Some(Date::new(2024, 2, 29)), None])
+        );
+        assert!(
+            matches!(batch.column_by_name("count"), Some(Column::Integer(v))
+            if v == &[Some(-7), Some(0)])
+        );
+        assert!(
+            matches!(batch.column_by_name("amount"), Some(Column::Currency(v))
+            if v == &[Some(Currency::new(12_345_678)), Some(Currency::new(0))])
+        );
+        assert!(
+            matches!(batch.column_by_name("weight"), Some(Column::Float(v))
+            if v == &[Some(0.25), Some(0.0)])
+        );
+        assert!(
+            matches!(batch.column_by_name("updated"), Some(Column::Timestamp(v))
+            if v == &[Some(Timestamp::new(2024, 2, 29, 13, 45, 30, 250)), None])
+        );
+    }
+
+    #[test]
+    fn batches_and_projection() {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 5, 0),
+        ];
+        let records = (0..5)
+            .map(|i| vec![Value::Str(format!("item {}", i)), Value::Int(i)])
+            .collect::<Vec<_>>();
+
+        let mut batches = BatchIterator::new(table(Version::DBase3, fields, &records), 2).unwrap();
+        assert!(batches.set_projection(&["price"]).is_err());
+        batches.set_projection(&["qty", "name"]).unwrap();
+
+        let names = batches
+            .fields()
+            .into_iter()
+            .map(|f| f.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["QTY", "NAME"]);
+        let batches = batches.collect::<Result<Vec<_>, _>>().unwrap();
+        let rows = batches.iter().map(Batch::num_rows).collect::<Vec<_>>();
+        assert_eq!(rows, vec![2, 2, 1]);
+        assert!(matches!(batches[2].column(0), Some(Column::Integer(v)) if v == &[Some(4)]));
+    }
+
+    #[test]
+    fn zero_batch_size_is_rejected() {
+        let fields = vec![FieldInfo::with_type("NAME", FieldType::Character, 10, 0)];
+        assert!(BatchIterator::new(table(Version::DBase3, fields, &[]), 0).is_err());
+    }
+
+    #[test]
+    fn integers_too_long_for_i64_are_decimals() {
+        let fields = vec![FieldInfo::with_type("ID", FieldType::Numeric, 20, 0)];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        writer.write_record(&[Value::Int(0)]).unwrap();
+        let mut table = writer.finish().unwrap().into_inner();
+        // the first field of the first record starts after the header and the deletion flag
+        table[66..86].copy_from_slice(b"99999999999999999999");
+
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+        let batch = BatchIterator::new(reader, 10)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(
+            batch.column(0),
+            Some(Column::Decimal(values)) if values == &[Some(99_999_999_999_999_999_999)]
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+
+use dbf_dextractor::{FieldInfo, Record, TableDiff, Value};
+
+use crate::export::format_value;
+use crate::{CliResult, OutputFormat};
+
+#[derive(Args)]
+pub struct DiffArgs {
+    /// Path of the old dbf file
+    old: PathBuf,
+    /// Path of the new dbf file
+    new: PathBuf,
+    /// Comma separated list of the columns identifying a record
+    #[arg(long, required = true, value_delimiter = ',')]
+    key: Vec<String>,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+pub fn run(args: DiffArgs) -> CliResult {
+    let diff = dbf_dextractor::diff(&args.old, &args.new, &args.key)?;
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    match args.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &diff)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Text => write_text(&mut stdout, &diff, &args.key)?,
+    }
+
+    Ok(())
+}
+
+fn find_field<'a>(fields: &'a [FieldInfo], name: &str) -> Option<&'a FieldInfo> {
+    fields.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+}
+
+fn describe_field(field: &FieldInfo) -> String {
+    format!(
+        "{} {}({},{})",
+        field.name,
+        field.type_code() as char,
+        field.length,
+        field.decimal_count
+    )
+}
+
+fn format_field_value(fields: &[FieldInfo], name: &str, value: &Value) -> String {
+    match find_field(fields, name) {
+        Some(field) => format!("{}={}", field.name, format_value(field, value)),
+        None => format!("{}={:?}", name, value),
+    }
+}
+
+fn format_record(record: &Record) -> String {
+    record
+        .fields()
+        .iter()
+        .zip(record.values())
+        .map(|(field, value)| format!("{}={}", field.name, format_value(field, value)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_text<W: Write>(writer: &mut W, diff: &TableDiff, key: &[String]) -> io::Result<()> {
+    for field in diff.schema.added.iter() {
+        writeln!(writer, "+ field {}", describe_field(field))?;
+    }
+    for field in diff.schema.removed.iter() {
+        writeln!(writer, "- field {}", describe_field(field))?;
+    }
+    for change in diff.schema.changed.iter() {
+        writeln!(
+            writer,
+            "~ field {} -> {}",
+            describe_field(&change.before),
+            describe_field(&change.after)
+        )?;
+    }
+
+    for record in diff.inserted.iter() {
+        writeln!(writer, "+ {}", format_record(record))?;
+    }
+    for record in diff.deleted.iter() {
+        writeln!(writer, "- {}", format_record(record))?;
+    }
+    for change in diff.changed.iter() {
+        let key = key
+            .iter()
+            .zip(change.key.iter())
+            .map(|(name, value)| format_field_value(&diff.fields, name, value))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writeln!(writer, "~ {}", key)?;
+        for value in change.changes.iter() {
+            writeln!(
+                writer,
+                "    {} -> {}",
+                format_field_value(&diff.fields, &value.field, &value.before),
+                format_value(
+                    find_field(&diff.fields, &value.field).expect("changed field exists"),
+                    &value.after
+                )
+            )?;
+        }
+    }
+
+    writeln!(
+        writer,
+        "{} inserted, {} deleted, {} changed",
+        diff.inserted.len(),
+        diff.deleted.len(),
+        diff.changed.len()
+    )
+}
@@ -0,0 +1,182 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::{Args, ValueEnum};
+
+use dbf_dextractor::{
+    CsvOptions, CsvWriter, Encoding, FieldInfo, FieldType, JsonFormat, JsonOptions, JsonWriter,
+    Record, Value,
+};
+
+use crate::table::TableWriter;
+use crate::CliResult;
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Ndjson,
+    Table,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Path of the dbf file
+    table: PathBuf,
+    /// Path of the memo file, looked up next to the table when not given
+    #[arg(long)]
+    memo: Option<PathBuf>,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "csv")]
+    format: ExportFormat,
+    /// Write to this file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Comma separated list of the columns to export
+    #[arg(long, value_delimiter = ',')]
+    columns: Option<Vec<String>>,
+    /// Maximum number of records to export
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Number of records to skip
+    #[arg(long, default_value_t = 0)]
+    offset: usize,
+    /// Also export records marked as deleted
+    #[arg(long)]
+    include_deleted: bool,
+    /// Encoding of character and memo fields, e.g. cp1252 or latin1 (defaults to the code page
+    /// of the table, or UTF-8)
+    #[arg(long, value_parser = parse_encoding)]
+    encoding: Option<Encoding>,
+}
+
+pub fn parse_encoding(label: &str) -> Result<Encoding, String> {
+    Encoding::for_label(label).ok_or_else(|| format!("unknown encoding '{}'", label))
+}
+
+pub fn run(args: ExportArgs) -> CliResult {
+    let memo_path = crate::memo_path(&args.table, &args.memo);
+    let mut records = dbf_dextractor::read_records(args.table.clone(), memo_path)?;
+    records.set_include_deleted(args.include_deleted);
+    if let Some(encoding) = args.encoding {
+        records.set_encoding(encoding);
+    }
+    if let Some(columns) = &args.columns {
+        records.set_projection(columns)?;
+    }
+
+    let fields = records.fields().to_vec();
+    let records = records
+        .skip(args.offset)
+        .take(args.limit.unwrap_or(usize::MAX));
+
+    write_records(
+        records,
+        &fields,
+        args.format,
+        args.output.as_deref(),
+        args.include_deleted,
+    )
+}
+
+pub fn write_records<I>(
+    records: I,
+    fields: &[FieldInfo],
+    format: ExportFormat,
+    output: Option<&Path>,
+    include_deleted: bool,
+) -> CliResult
+where
+    I: Iterator<Item = Result<Record, Box<dyn std::error::Error>>>,
+{
+    let stdout = io::stdout();
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(stdout.lock()),
+    };
+
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = CsvWriter::new(writer, fields, CsvOptions::default());
+            writer.write_header()?;
+            for record in records {
+                writer.write_row(&record?)?;
+            }
+            writer.flush()?;
+        }
+        ExportFormat::Json | ExportFormat::Ndjson => {
+            let options = JsonOptions {
+                format: match format {
+                    ExportFormat::Json => JsonFormat::Array,
+                    _ => JsonFormat::Lines,
+                },
+                include_deleted,
+                ..Default::default()
+            };
+            let mut writer = JsonWriter::new(writer, options);
+            for record in records {
+                writer.write_record(&record?)?;
+            }
+            writer.finish()?;
+        }
+        ExportFormat::Table => {
+            let headers = fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+            let widths = fields.iter().map(display_width).collect();
+            let mut table = TableWriter::new(writer, &headers, widths)?;
+            for record in records {
+                table.write_row(&format_record(fields, &record?))?;
+            }
+            table.into_inner().flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+// the width of the values of the field as formatted by format_value
+fn display_width(field: &FieldInfo) -> usize {
+    match field.field_type {
+        FieldType::Character | FieldType::Varchar | FieldType::Numeric | FieldType::Float => {
+            field.length
+        }
+        FieldType::Logical => "false".len(),
+        FieldType::Integer => "-2147483648".len(),
+        FieldType::Currency => "-922337203685477.5808".len(),
+        FieldType::Date => "2024-02-29".len(),
+        FieldType::Timestamp => "2024-02-29T13:45:30.250".len(),
+        // memo and binary values vary too much in length to reserve space for them
+        FieldType::Double
+        | FieldType::Memo
+        | FieldType::Binary
+        | FieldType::General
+        | FieldType::Varbinary => 0,
+    }
+}
+
+fn format_record(fields: &[FieldInfo], record: &Record) -> Vec<String> {
+    fields
+        .iter()
+        .zip(record.values())
+        .map(|(field, value)| format_value(field, value))
+        .collect()
+}
+
+pub fn format_value(field: &FieldInfo, value: &Value) -> String {
+    match value {
+        Value::Str(val) => val.clone(),
+        Value::Int(val) => val.to_string(),
+        Value::Float(val) => match field.field_type {
+            FieldType::Numeric | FieldType::Float => {
+                format!("{:.1$}", val, field.decimal_count)
+            }
+            _ => val.to_string(),
+        },
+        Value::Currency(val) => val.to_string(),
+        Value::Bool(val) => val.to_string(),
+        Value::Date(val) => val.to_string(),
+        Value::Timestamp(val) => val.to_string(),
+        Value::Bytes(val) => format!("<{} bytes>", val.len()),
+        Value::Null => String::new(),
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use dbf_dextractor::TableInfo;
+
+use crate::{CliResult, OutputFormat};
+
+#[derive(Args)]
+pub struct InfoArgs {
+    /// Path of the dbf file
+    table: PathBuf,
+    /// Path of the memo file, looked up next to the table when not given
+    #[arg(long)]
+    memo: Option<PathBuf>,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct InfoOutput<'a> {
+    table: &'a PathBuf,
+    table_size: u64,
+    memo_file: Option<&'a PathBuf>,
+    memo_size: Option<u64>,
+    #[serde(flatten)]
+    info: &'a TableInfo,
+}
+
+pub fn run(args: InfoArgs) -> CliResult {
+    let memo_path = crate::memo_path(&args.table, &args.memo);
+    let info = dbf_dextractor::table_info(&args.table, memo_path.as_ref())?;
+
+    let output = InfoOutput {
+        table: &args.table,
+        table_size: fs::metadata(&args.table)?.len(),
+        memo_file: memo_path.as_ref(),
+        memo_size: memo_path
+            .as_ref()
+            .map(|p| fs::metadata(p).map(|m| m.len()))
+            .transpose()?,
+        info: &info,
+    };
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    match args.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &output)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Text => write_text(&mut stdout, &output)?,
+    }
+
+    Ok(())
+}
+
+fn write_text<W: Write>(writer: &mut W, output: &InfoOutput) -> io::Result<()> {
+    let info = output.info;
+    let code_page = match dbf_dextractor::code_page(info.code_page) {
+        Some((code_page, name)) => format!("0x{:02x} ({} {})", info.code_page, code_page, name),
+        None => format!("0x{:02x}", info.code_page),
+    };
+
+    writeln!(writer, "File:            {}", output.table.display())?;
+    writeln!(writer, "File size:       {} bytes", output.table_size)?;
+    writeln!(writer, "Version:         {:?}", info.version)?;
+    writeln!(writer, "Last update:     {}", info.last_update)?;
+    writeln!(
+        writer,
+        "Records:         {} ({} deleted)",
+        info.record_count, info.deleted_count
+    )?;
+    writeln!(writer, "Header length:   {}", info.header_length)?;
+    writeln!(writer, "Record length:   {}", info.record_length)?;
+    writeln!(writer, "Fields:          {}", info.fields.len())?;
+    writeln!(writer, "Code page:       {}", code_page)?;
+    if let Some(language_driver) = &info.language_driver {
+        writeln!(writer, "Language driver: {}", language_driver)?;
+    }
+    if !info.properties.is_empty() {
+        writeln!(writer, "Properties:      {}", info.properties.len())?;
+    }
+
+    match (output.memo_file, &info.memo) {
+        (Some(memo_file), Some(memo)) => {
+            writeln!(writer, "Memo file:       {}", memo_file.display())?;
+            if let Some(memo_size) = output.memo_size {
+                writeln!(writer, "Memo file size:  {} bytes", memo_size)?;
+            }
+            writeln!(writer, "Memo block size: {}", memo.block_size)?;
+            writeln!(writer, "Memo next block: {}", memo.next_block)?;
+        }
+        _ => writeln!(writer, "Memo file:       none")?,
+    }
+
+    Ok(())
+}
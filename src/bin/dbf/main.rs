@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+use std::process;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+mod diff;
+mod export;
+mod info;
+mod query;
+mod schema;
+mod table;
+mod validate;
+
+#[derive(Parser)]
+#[command(name = "dbf", version, about = "Inspect and export dbf files")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the table header and memo file details
+    Info(info::InfoArgs),
+    /// Print the field descriptors of the table
+    Schema(schema::SchemaArgs),
+    /// Export the records as CSV, JSON, NDJSON or an aligned table
+    #[command(alias = "cat")]
+    Export(export::ExportArgs),
+    /// Check the table and memo file for structural problems
+    Validate(validate::ValidateArgs),
+    /// Compare two snapshots of a table by key
+    Diff(diff::DiffArgs),
+    /// Print the records matching a dBase expression
+    Query(query::QueryArgs),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+pub type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+pub fn memo_path(table_path: &Path, memo_path: &Option<PathBuf>) -> Option<PathBuf> {
+    memo_path
+        .clone()
+        .or_else(|| dbf_dextractor::find_memo_file(table_path))
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Info(args) => info::run(args),
+        Command::Schema(args) => schema::run(args),
+        Command::Export(args) => export::run(args),
+        Command::Validate(args) => validate::run(args),
+        Command::Diff(args) => diff::run(args),
+        Command::Query(args) => query::run(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("dbf: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use dbf_dextractor::{Encoding, Filter};
+
+use crate::export::{parse_encoding, write_records, ExportFormat};
+use crate::CliResult;
+
+#[derive(Args)]
+pub struct QueryArgs {
+    /// Path of the dbf file
+    table: PathBuf,
+    /// Path of the memo file, looked up next to the table when not given
+    #[arg(long)]
+    memo: Option<PathBuf>,
+    /// dBase expression selecting the records, e.g. "STATE = 'SP' .AND. TOTAL > 100"
+    #[arg(long = "where", short = 'w')]
+    filter: String,
+    /// Comma separated list of the columns to print
+    #[arg(long, short, value_delimiter = ',')]
+    select: Option<Vec<String>>,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "table")]
+    format: ExportFormat,
+    /// Write to this file instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    /// Maximum number of records to print
+    #[arg(long)]
+    limit: Option<usize>,
+    /// Also query records marked as deleted
+    #[arg(long)]
+    include_deleted: bool,
+    /// Encoding of character and memo fields, e.g. cp1252 or latin1 (defaults to the code page
+    /// of the table, or UTF-8)
+    #[arg(long, value_parser = parse_encoding)]
+    encoding: Option<Encoding>,
+}
+
+pub fn run(args: QueryArgs) -> CliResult {
+    let filter = Filter::parse(&args.filter)?;
+
+    let memo_path = crate::memo_path(&args.table, &args.memo);
+    let mut records = dbf_dextractor::read_records(args.table.clone(), memo_path)?;
+    records.set_include_deleted(args.include_deleted);
+    if let Some(encoding) = args.encoding {
+        records.set_encoding(encoding);
+    }
+    records.set_filter(filter);
+    if let Some(columns) = &args.select {
+        records.set_projection(columns)?;
+    }
+
+    let fields = records.fields().to_vec();
+    let records = records.take(args.limit.unwrap_or(usize::MAX));
+
+    write_records(
+        records,
+        &fields,
+        args.format,
+        args.output.as_deref(),
+        args.include_deleted,
+    )
+}
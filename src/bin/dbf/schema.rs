@@ -0,0 +1,70 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+
+use dbf_dextractor::FieldInfo;
+
+use crate::table::write_table;
+use crate::{CliResult, OutputFormat};
+
+#[derive(Args)]
+pub struct SchemaArgs {
+    /// Path of the dbf file
+    table: PathBuf,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+pub fn run(args: SchemaArgs) -> CliResult {
+    let records = dbf_dextractor::read_records(&args.table, None)?;
+    let fields = records.fields();
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    match args.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, fields)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Text => {
+            let headers = ["Name", "Type", "Length", "Decimals", "Offset", "Flags"]
+                .iter()
+                .map(|h| h.to_string())
+                .collect::<Vec<_>>();
+            let rows = fields
+                .iter()
+                .map(|f| {
+                    vec![
+                        f.name.clone(),
+                        format!("{} ({})", f.field_type, f.type_code() as char),
+                        f.length.to_string(),
+                        f.decimal_count.to_string(),
+                        f.offset.to_string(),
+                        format_flags(f),
+                    ]
+                })
+                .collect::<Vec<_>>();
+            write_table(&mut stdout, &headers, &rows)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn format_flags(field: &FieldInfo) -> String {
+    let flags = [
+        (field.flags.system, "system"),
+        (field.flags.nullable, "nullable"),
+        (field.flags.binary, "binary"),
+        (field.autoincrement.is_some(), "autoincrement"),
+    ];
+
+    flags
+        .iter()
+        .filter(|(set, _)| *set)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
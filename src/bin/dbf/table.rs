@@ -0,0 +1,65 @@
+use std::io::{self, Write};
+
+pub fn write_table<W: Write>(
+    writer: &mut W,
+    headers: &[String],
+    rows: &[Vec<String>],
+) -> io::Result<()> {
+    let mut widths = headers
+        .iter()
+        .map(|h| h.chars().count())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = TableWriter::new(writer, headers, widths)?;
+    for row in rows {
+        table.write_row(row)?;
+    }
+
+    Ok(())
+}
+
+// writes rows as they come, so the widths must be known up front; longer cells shift the rest of
+// their row
+pub struct TableWriter<W: Write> {
+    writer: W,
+    widths: Vec<usize>,
+}
+
+impl<W: Write> TableWriter<W> {
+    pub fn new(mut writer: W, headers: &[String], widths: Vec<usize>) -> io::Result<Self> {
+        let widths = headers
+            .iter()
+            .zip(widths)
+            .map(|(header, width)| width.max(header.chars().count()))
+            .collect::<Vec<_>>();
+
+        write_row(&mut writer, headers, &widths)?;
+        let separator = widths.iter().map(|w| "-".repeat(*w)).collect::<Vec<_>>();
+        write_row(&mut writer, &separator, &widths)?;
+
+        Ok(Self { writer, widths })
+    }
+
+    pub fn write_row(&mut self, cells: &[String]) -> io::Result<()> {
+        write_row(&mut self.writer, cells, &self.widths)
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+fn write_row<W: Write>(writer: &mut W, cells: &[String], widths: &[usize]) -> io::Result<()> {
+    let line = cells
+        .iter()
+        .zip(widths)
+        .map(|(cell, width)| format!("{:<1$}", cell, width))
+        .collect::<Vec<_>>()
+        .join("  ");
+    writeln!(writer, "{}", line.trim_end())
+}
@@ -0,0 +1,53 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+use clap::Args;
+
+use crate::{CliResult, OutputFormat};
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// Path of the dbf file
+    table: PathBuf,
+    /// Path of the memo file, looked up next to the table when not given
+    #[arg(long)]
+    memo: Option<PathBuf>,
+    /// Output format
+    #[arg(long, short, value_enum, default_value = "text")]
+    format: OutputFormat,
+}
+
+pub fn run(args: ValidateArgs) -> CliResult {
+    let memo_path = crate::memo_path(&args.table, &args.memo);
+    let report = dbf_dextractor::validate(&args.table, memo_path.as_ref())?;
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+
+    match args.format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut stdout, &report)?;
+            writeln!(stdout)?;
+        }
+        OutputFormat::Text => {
+            writeln!(
+                stdout,
+                "{}: {} records checked, {} problem(s) found",
+                args.table.display(),
+                report.records_checked,
+                report.issues.len()
+            )?;
+            for issue in report.issues.iter() {
+                writeln!(stdout, "  - {}", issue)?;
+            }
+        }
+    }
+
+    if !report.is_valid() {
+        stdout.flush()?;
+        process::exit(2);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use std::borrow::Cow;
+use std::convert::TryFrom;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Latin1,
+    #[cfg(feature = "encoding")]
+    Other(&'static encoding_rs::Encoding),
+}
+
+impl Encoding {
+    pub fn for_label(label: &str) -> Option<Self> {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Encoding::Utf8),
+            "latin1" | "iso-8859-1" => Some(Encoding::Latin1),
+            #[cfg(feature = "encoding")]
+            label => encoding_rs::Encoding::for_label(label.as_bytes()).map(Encoding::Other),
+            #[cfg(not(feature = "encoding"))]
+            _ => None,
+        }
+    }
+
+    // encodings matching the code page mark of the header, when one is available
+    pub fn for_code_page(code_page_mark: u8) -> Option<Self> {
+        #[cfg(feature = "encoding")]
+        {
+            let (code_page, _) = crate::info::code_page(code_page_mark)?;
+            let encoding = match code_page {
+                866 => encoding_rs::IBM866,
+                874 => encoding_rs::WINDOWS_874,
+                932 => encoding_rs::SHIFT_JIS,
+                936 => encoding_rs::GBK,
+                949 => encoding_rs::EUC_KR,
+                950 => encoding_rs::BIG5,
+                1250..=1258 => {
+                    encoding_rs::Encoding::for_label(format!("windows-{}", code_page).as_bytes())?
+                }
+                10000 => encoding_rs::MACINTOSH,
+                10007 => encoding_rs::X_MAC_CYRILLIC,
+                _ => return None,
+            };
+            Some(Encoding::Other(encoding))
+        }
+        #[cfg(not(feature = "encoding"))]
+        {
+            let _ = code_page_mark;
+            None
+        }
+    }
+
+    // None when the text has characters the encoding cannot represent
+    pub fn encode<'a>(&self, text: &'a str) -> Option<Cow<'a, [u8]>> {
+        match self {
+            Encoding::Utf8 => Some(Cow::Borrowed(text.as_bytes())),
+            Encoding::Latin1 => text
+                .chars()
+                .map(|c| u8::try_from(c).ok())
+                .collect::<Option<Vec<_>>>()
+                .map(Cow::Owned),
+            #[cfg(feature = "encoding")]
+            Encoding::Other(encoding) => match encoding.encode(text) {
+                (_, _, true) => None,
+                (bytes, _, false) => Some(bytes),
+            },
+        }
+    }
+
+    pub fn decode<'a>(&self, buf: &'a [u8]) -> Cow<'a, str> {
+        match self {
+            Encoding::Utf8 => String::from_utf8_lossy(buf),
+            Encoding::Latin1 => Cow::Owned(buf.iter().map(|b| *b as char).collect()),
+            #[cfg(feature = "encoding")]
+            Encoding::Other(encoding) => encoding.decode_without_bom_handling(buf).0,
+        }
+    }
+}
@@ -1,38 +1,151 @@
 use std::convert::TryFrom;
 use std::fmt;
+
+use serde::Serialize;
+
+use super::version::Version;
 use crate::error::UnsupportedFieldTypeError;
 
 const FIELD_DESCRIPTOR_LENGTH: usize = 32;
+const DBASE7_FIELD_DESCRIPTOR_LENGTH: usize = 48;
+const MAX_INTEGRAL_NUMERIC_LENGTH: usize = 18;
 
-pub fn read_field_info(buf: &[u8]) -> Result<Vec<FieldInfo>, UnsupportedFieldTypeError> {
-    buf.chunks(FIELD_DESCRIPTOR_LENGTH)
-        .scan(1usize, |acc_offset, info| {
-            if info.len() < FIELD_DESCRIPTOR_LENGTH {
-                return None;
-            }
+pub(crate) const NULL_FLAGS_TYPE: u8 = b'0';
+const SYSTEM_FLAG: u8 = 0x01;
+const NULLABLE_FLAG: u8 = 0x02;
+const BINARY_FLAG: u8 = 0x04;
+pub(crate) const AUTOINCREMENT_FLAGS: u8 = 0x0c;
 
-            if info[0] == b'\r' {
-                return None;
-            }
+pub fn read_field_info(
+    buf: &[u8],
+    version: Version,
+) -> Result<(Vec<FieldInfo>, Option<NullFlags>), UnsupportedFieldTypeError> {
+    let mut fields = Vec::new();
+    let mut null_flags = None;
+    let mut offset = 1;
 
-            let name_bytes = &info[0..=10];
-            let field_type = info[11];
-            let length = info[16] as usize;
-            let offset = *acc_offset;
-            *acc_offset += length;
+    let descriptor_length = field_descriptor_length(version);
+    for info in buf.chunks(descriptor_length) {
+        if info.len() < descriptor_length || info[0] == b'\r' {
+            break;
+        }
 
-            match FieldInfo::new(name_bytes, field_type, length, offset) {
-                Ok(value) => Some(Ok(value)),
-                Err(e) => Some(Err(e)),
+        let (name_bytes, info) = match version {
+            Version::DBase7 => info.split_at(32),
+            _ => info.split_at(11),
+        };
+        let field_type = info[0];
+        let (length, decimal_count, flags) = match version {
+            Version::DBase7 => (info[1] as usize, info[2] as usize, 0),
+            _ if field_type == b'C' && has_extended_character_length(version) => {
+                (info[5] as usize | (info[6] as usize) << 8, 0, info[7])
             }
-        })
-        .collect()
+            _ => (info[5] as usize, info[6] as usize, info[7]),
+        };
+
+        // the hidden _NullFlags column holds the null and length bits of the other fields
+        if field_type == NULL_FLAGS_TYPE && flags & SYSTEM_FLAG != 0 {
+            null_flags = Some(NullFlags { offset, length });
+        } else {
+            let mut field = FieldInfo::new(
+                name_bytes,
+                field_type,
+                version,
+                length,
+                decimal_count,
+                offset,
+            )?;
+            field.flags = FieldFlags::from_bits(flags);
+            field.autoincrement = read_autoincrement(info, field_type, flags, version);
+            fields.push(field);
+        }
+
+        offset += length;
+    }
+
+    // Visual FoxPro gives variable length fields a length bit before the null bit of nullable
+    // fields, in field order
+    let mut bit = 0;
+    for field in fields.iter_mut() {
+        if field.field_type.is_variable_length() {
+            field.varlength_bit = Some(bit);
+            bit += 1;
+        }
+        if field.flags.nullable {
+            field.null_bit = Some(bit);
+            bit += 1;
+        }
+    }
+
+    Ok((fields, null_flags))
+}
+
+// the next value is stored at byte 19 (Visual FoxPro) or 40 (dBase 7) of the descriptor, and
+// only Visual FoxPro stores a step
+fn read_autoincrement(
+    info: &[u8],
+    field_type: u8,
+    flags: u8,
+    version: Version,
+) -> Option<Autoincrement> {
+    let next_value = |info: &[u8]| i32::from_le_bytes([info[8], info[9], info[10], info[11]]);
+    match version {
+        Version::VisualFoxPro if flags & AUTOINCREMENT_FLAGS == AUTOINCREMENT_FLAGS => {
+            Some(Autoincrement {
+                next: next_value(info),
+                step: info[12] as i32,
+            })
+        }
+        Version::DBase7 if field_type == b'+' => Some(Autoincrement {
+            next: next_value(info),
+            step: 1,
+        }),
+        _ => None,
+    }
+}
+
+// Clipper and FoxPro use the decimal count of character fields as the high byte of their length
+pub(crate) fn has_extended_character_length(version: Version) -> bool {
+    matches!(
+        version,
+        Version::FoxBase | Version::DBase3 | Version::FoxPro2 | Version::VisualFoxPro
+    )
+}
+
+pub fn field_descriptor_length(version: Version) -> usize {
+    match version {
+        Version::DBase7 => DBASE7_FIELD_DESCRIPTOR_LENGTH,
+        _ => FIELD_DESCRIPTOR_LENGTH,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NullFlags {
+    offset: usize,
+    length: usize,
+}
+
+impl NullFlags {
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub(crate) fn end(&self) -> usize {
+        self.offset + self.length
+    }
+
+    pub fn is_set(&self, record_buf: &[u8], bit: usize) -> bool {
+        let byte = bit / 8;
+        byte < self.length && record_buf[self.offset + byte] & (1 << (bit % 8)) != 0
+    }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub enum FieldType {
     Logical,
     Character,
+    Currency,
+    Double,
     Integer,
     Numeric,
     Float,
@@ -41,6 +154,40 @@ pub enum FieldType {
     Memo,
     Binary,
     General,
+    Varchar,
+    Varbinary,
+}
+
+impl FieldType {
+    pub fn code(&self) -> u8 {
+        match self {
+            FieldType::Binary => b'B',
+            FieldType::Character => b'C',
+            FieldType::Currency => b'Y',
+            FieldType::Date => b'D',
+            FieldType::Double => b'B',
+            FieldType::Float => b'F',
+            FieldType::General => b'G',
+            FieldType::Integer => b'I',
+            FieldType::Logical => b'L',
+            FieldType::Memo => b'M',
+            FieldType::Numeric => b'N',
+            FieldType::Timestamp => b'T',
+            FieldType::Varbinary => b'Q',
+            FieldType::Varchar => b'V',
+        }
+    }
+
+    pub fn is_memo(&self) -> bool {
+        matches!(
+            self,
+            FieldType::Memo | FieldType::Binary | FieldType::General
+        )
+    }
+
+    pub fn is_variable_length(&self) -> bool {
+        matches!(self, FieldType::Varchar | FieldType::Varbinary)
+    }
 }
 
 impl fmt::Display for FieldType {
@@ -48,20 +195,39 @@ impl fmt::Display for FieldType {
         let name = match self {
             FieldType::Logical => "Logical",
             FieldType::Character => "Character",
+            FieldType::Currency => "Currency",
             FieldType::Integer => "Integer",
             FieldType::Numeric => "Numeric",
             FieldType::Float => "Float",
             FieldType::Date => "Date",
+            FieldType::Double => "Double",
             FieldType::Timestamp => "Timestamp",
             FieldType::Memo => "Memo",
             FieldType::Binary => "Binary",
             FieldType::General => "General",
+            FieldType::Varchar => "Varchar",
+            FieldType::Varbinary => "Varbinary",
         };
 
         f.write_str(name)
     }
 }
 
+impl FieldType {
+    pub fn from_code(code: u8, version: Version) -> Result<Self, UnsupportedFieldTypeError> {
+        match (code, version) {
+            // Visual FoxPro stores doubles inline where dBase has binary memos
+            (b'B', Version::VisualFoxPro) => Ok(FieldType::Double),
+            // dBase 7 autoincrement fields are integers, and its doubles and timestamps are
+            // stored as sortable binary doubles
+            (b'+', Version::DBase7) => Ok(FieldType::Integer),
+            (b'O', Version::DBase7) => Ok(FieldType::Double),
+            (b'@', Version::DBase7) => Ok(FieldType::Timestamp),
+            (code, _) => FieldType::try_from(code),
+        }
+    }
+}
+
 impl TryFrom<u8> for FieldType {
     type Error = UnsupportedFieldTypeError;
 
@@ -77,25 +243,76 @@ impl TryFrom<u8> for FieldType {
             'L' => Ok(FieldType::Logical),
             'M' => Ok(FieldType::Memo),
             'N' => Ok(FieldType::Numeric),
+            'Q' => Ok(FieldType::Varbinary),
             'T' => Ok(FieldType::Timestamp),
+            'V' => Ok(FieldType::Varchar),
+            'Y' => Ok(FieldType::Currency),
             _ => Err(UnsupportedFieldTypeError(value)),
         }
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub struct FieldFlags {
+    pub system: bool,
+    pub nullable: bool,
+    pub binary: bool,
+}
+
+impl FieldFlags {
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            system: bits & SYSTEM_FLAG != 0,
+            nullable: bits & NULLABLE_FLAG != 0,
+            binary: bits & BINARY_FLAG != 0,
+        }
+    }
+
+    pub fn bits(&self) -> u8 {
+        let mut bits = 0;
+        if self.system {
+            bits |= SYSTEM_FLAG;
+        }
+        if self.nullable {
+            bits |= NULLABLE_FLAG;
+        }
+        if self.binary {
+            bits |= BINARY_FLAG;
+        }
+        bits
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Autoincrement {
+    pub next: i32,
+    pub step: i32,
+}
+
+#[derive(Clone, Debug, Serialize)]
 pub struct FieldInfo {
     pub name: String,
     pub field_type: FieldType,
     pub length: usize,
+    pub decimal_count: usize,
     pub offset: usize,
+    pub flags: FieldFlags,
+    pub autoincrement: Option<Autoincrement>,
+    #[serde(skip)]
+    pub(crate) code: Option<u8>,
+    #[serde(skip)]
+    pub(crate) varlength_bit: Option<usize>,
+    #[serde(skip)]
+    pub(crate) null_bit: Option<usize>,
 }
 
 impl FieldInfo {
     pub fn new(
         name_bytes: &[u8],
         field_type: u8,
+        version: Version,
         length: usize,
+        decimal_count: usize,
         offset: usize,
     ) -> Result<Self, UnsupportedFieldTypeError> {
         let name = name_bytes
@@ -107,25 +324,66 @@ impl FieldInfo {
                 acc
             });
 
-        let field_type = FieldType::try_from(field_type)?;
+        let code = field_type;
+        let field_type = FieldType::from_code(code, version)?;
 
         Ok(Self {
             name,
             field_type,
             length,
+            decimal_count,
             offset,
+            autoincrement: None,
+            flags: FieldFlags::default(),
+            code: Some(code),
+            varlength_bit: None,
+            null_bit: None,
         })
     }
+
+    /// Numeric fields without decimals that fit in an `i64`
+    pub fn is_integral(&self) -> bool {
+        self.field_type == FieldType::Numeric
+            && self.decimal_count == 0
+            && self.length <= MAX_INTEGRAL_NUMERIC_LENGTH
+    }
+
+    /// Type code of the field descriptor, which for dBase 7 `+`, `O` and `@` fields differs from
+    /// `FieldType::code`
+    pub fn type_code(&self) -> u8 {
+        self.code.unwrap_or_else(|| self.field_type.code())
+    }
+
+    pub fn with_type<S: Into<String>>(
+        name: S,
+        field_type: FieldType,
+        length: usize,
+        decimal_count: usize,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            field_type,
+            length,
+            decimal_count,
+            offset: 0,
+            autoincrement: None,
+            flags: FieldFlags::default(),
+            code: None,
+            varlength_bit: None,
+            null_bit: None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub enum FieldValue {
     Binary(Vec<u8>),
     Character(String),
+    Currency(i64),
     Date(u16, u8, u8),
     Float(f64),
     General(Vec<u8>),
-    Integer(i32),
+    Integer(i64),
     Numeric(f64),
     Logical(bool),
     Memo(String),
@@ -1,11 +1,15 @@
 use std::convert::TryFrom;
-use std::io::Read;
+use std::io::{Error as IoError, ErrorKind, Read};
 
 use byteorder::{LittleEndian, ReadBytesExt};
 
 use super::version::Version;
 use crate::model::Date;
 
+const HEADER_LENGTH: usize = 32;
+const DBASE7_HEADER_LENGTH: usize = 68;
+const LANGUAGE_DRIVER_LENGTH: usize = 32;
+
 #[derive(Debug)]
 pub struct Header {
     pub version: Version,
@@ -13,11 +17,14 @@ pub struct Header {
     pub record_count: u32,
     pub header_length: usize,
     pub record_length: usize,
+    pub code_page: u8,
+    pub language_driver: Option<String>,
 }
 
 impl Header {
-    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Self, std::io::Error> {
-        let version = Version::try_from(reader.read_u8()?).unwrap();
+    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Self, IoError> {
+        let version = Version::try_from(reader.read_u8()?)
+            .map_err(|e| IoError::new(ErrorKind::InvalidData, e))?;
         let last_update = Date::new(
             reader.read_u8()? as u16 + 1900,
             reader.read_u8()?,
@@ -26,6 +33,18 @@ impl Header {
         let record_count = reader.read_u32::<LittleEndian>()?;
         let header_length = reader.read_u16::<LittleEndian>()? as usize;
         let record_length = reader.read_u16::<LittleEndian>()? as usize;
+        let mut reserved = [0u8; 17];
+        reader.read_exact(&mut reserved)?;
+        let code_page = reader.read_u8()?;
+
+        let language_driver = if version == Version::DBase7 {
+            let mut buf = [0u8; 2 + LANGUAGE_DRIVER_LENGTH];
+            reader.read_exact(&mut buf)?;
+            let name = buf[2..].iter().take_while(|b| **b != 0).map(|b| *b as char);
+            Some(name.collect())
+        } else {
+            None
+        };
 
         Ok(Self {
             version,
@@ -33,6 +52,15 @@ impl Header {
             record_count,
             header_length,
             record_length,
+            code_page,
+            language_driver,
         })
     }
+
+    pub fn field_descriptors_offset(&self) -> usize {
+        match self.version {
+            Version::DBase7 => DBASE7_HEADER_LENGTH,
+            _ => HEADER_LENGTH,
+        }
+    }
 }
@@ -8,14 +8,20 @@ pub struct MemoReader<R: Read + Seek> {
     reader: R,
     version: Version,
     block_size: u16,
+    next_block: u32,
 }
 
 impl<R: Read + Seek> MemoReader<R> {
     pub fn from_reader(mut reader: R, version: Version) -> Result<Self, IoError> {
-        reader.seek(SeekFrom::Current(4))?;
+        let next_block = match version {
+            Version::DBase3 | Version::DBase4 | Version::DBase7 => {
+                reader.read_u32::<LittleEndian>()?
+            }
+            _ => reader.read_u32::<BigEndian>()?,
+        };
         let block_size = match version {
             Version::DBase3 => 512,
-            Version::DBase4 => match reader.read_u16::<LittleEndian>()? {
+            Version::DBase4 | Version::DBase7 => match reader.read_u16::<LittleEndian>()? {
                 0 => 512,
                 v => v,
             },
@@ -29,9 +35,18 @@ impl<R: Read + Seek> MemoReader<R> {
             reader,
             version,
             block_size,
+            next_block,
         })
     }
 
+    pub fn block_size(&self) -> u16 {
+        self.block_size
+    }
+
+    pub fn next_block(&self) -> u32 {
+        self.next_block
+    }
+
     pub fn read_memo(&mut self, index: u32) -> Result<Vec<u8>, IoError> {
         let offset = index as u64 * self.block_size as u64;
         self.reader.seek(SeekFrom::Start(offset))?;
@@ -51,13 +66,10 @@ impl<R: Read + Seek> MemoReader<R> {
                         break;
                     }
                 }
-                let end = buf
-                    .iter()
-                    .position(|b| *b == 0x1a)
-                    .unwrap_or_else(|| buf.len());
+                let end = buf.iter().position(|b| *b == 0x1a).unwrap_or(buf.len());
                 Ok(buf[..end].to_owned())
             }
-            Version::DBase4 => {
+            Version::DBase4 | Version::DBase7 => {
                 self.reader.seek(SeekFrom::Current(4))?; // reserved bytes
                 let length = self.reader.read_u32::<LittleEndian>()?;
                 let mut buf = vec![0u8; length as usize];
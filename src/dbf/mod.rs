@@ -1,12 +1,20 @@
+mod encoding;
 mod field;
 mod header;
 mod memo;
 mod parser;
+mod properties;
 mod reader;
 mod version;
+mod writer;
 
-pub use field::{FieldInfo, FieldType, FieldValue};
+pub use encoding::Encoding;
+pub use field::{
+    field_descriptor_length, Autoincrement, FieldFlags, FieldInfo, FieldType, FieldValue, NullFlags,
+};
 pub use header::Header;
 pub use memo::MemoReader;
+pub use properties::{FieldProperty, PropertyKind};
 pub use reader::DbfReader;
 pub use version::Version;
+pub use writer::DbfWriter;
@@ -2,27 +2,35 @@ use std::convert::TryInto;
 use std::error::Error as StdError;
 use std::io::{Read, Seek};
 
-use crate::dbf::{FieldInfo, FieldType, FieldValue, MemoReader};
-use crate::error::FieldParseError;
+use crate::dbf::{Encoding, FieldInfo, FieldType, FieldValue, MemoReader, NullFlags, Version};
+use crate::error::{FieldParseError, ParseDecimalError};
+
+const MILLISECONDS_PER_DAY: i64 = 86_400_000;
 
 pub fn parse_record<R>(
     fields: &[FieldInfo],
+    null_flags: Option<NullFlags>,
     buf: &[u8],
     memo_reader: &mut Option<MemoReader<R>>,
+    version: Version,
+    encoding: Encoding,
 ) -> Result<Vec<FieldValue>, FieldParseError>
 where
     R: Read + Seek,
 {
     fields
         .iter()
-        .map(|f| parse_field(f, buf, memo_reader))
+        .map(|f| parse_field(f, null_flags, buf, memo_reader, version, encoding))
         .collect()
 }
 
-fn parse_field<R>(
+pub fn parse_field<R>(
     field: &FieldInfo,
+    null_flags: Option<NullFlags>,
     record_buf: &[u8],
     memo_reader: &mut Option<MemoReader<R>>,
+    version: Version,
+    encoding: Encoding,
 ) -> Result<FieldValue, FieldParseError>
 where
     R: Read + Seek,
@@ -31,7 +39,14 @@ where
     let end = field.offset + field.length;
     let buf = &record_buf[start..end];
 
-    if buf.iter().all(|b| *b == b' ') || buf.iter().all(|b| *b == b'\0') {
+    if let (Some(null_flags), Some(bit)) = (null_flags, field.null_bit) {
+        if null_flags.is_set(record_buf, bit) {
+            return Ok(FieldValue::Null);
+        }
+    }
+
+    let is_blank = buf.iter().all(|b| *b == b' ') || buf.iter().all(|b| *b == b'\0');
+    if is_blank && blank_is_null(&field.field_type, version) {
         return Ok(FieldValue::Null);
     }
 
@@ -39,19 +54,46 @@ where
 
     match field.field_type {
         FieldType::Logical => Ok(parse_logic(buf)),
-        FieldType::Character => Ok(parse_character(buf)),
-        FieldType::Integer => parse_integer(buf).map_err(map_e),
+        FieldType::Character => Ok(parse_character(buf, encoding)),
+        FieldType::Currency => parse_currency(buf).map_err(map_e),
+        FieldType::Double => parse_double(buf, version).map_err(map_e),
+        FieldType::Integer => parse_integer(buf, version).map_err(map_e),
+        FieldType::Numeric if field.is_integral() => parse_integral(buf).map_err(map_e),
         FieldType::Numeric => parse_numeric(buf).map_err(map_e),
         FieldType::Float => parse_float(buf).map_err(map_e),
         FieldType::Date => parse_date(buf).map_err(map_e),
+        FieldType::Timestamp if version == Version::DBase7 => {
+            parse_dbase7_timestamp(buf).map_err(map_e)
+        }
         FieldType::Timestamp => parse_timestamp(buf).map_err(map_e),
-        FieldType::Memo => parse_memo(buf, memo_reader).map_err(map_e),
+        FieldType::Memo => parse_memo(buf, memo_reader, encoding).map_err(map_e),
         FieldType::Binary => parse_binary(buf, memo_reader).map_err(map_e),
         FieldType::General => parse_general(buf, memo_reader).map_err(map_e),
+        FieldType::Varchar | FieldType::Varbinary => {
+            let is_short = match (null_flags, field.varlength_bit) {
+                (Some(null_flags), Some(bit)) => null_flags.is_set(record_buf, bit),
+                _ => false,
+            };
+            Ok(parse_variable_length(field, buf, is_short, encoding))
+        }
+    }
+}
+
+// Visual FoxPro marks nulls in _NullFlags, so blank strings are empty and zeroed binary numbers are
+// zero there
+fn blank_is_null(field_type: &FieldType, version: Version) -> bool {
+    if field_type.is_variable_length() {
+        return false;
     }
+
+    !(version == Version::VisualFoxPro
+        && matches!(
+            field_type,
+            FieldType::Character | FieldType::Integer | FieldType::Currency | FieldType::Double
+        ))
 }
 
-fn memo_index(buf: &[u8]) -> Result<u32, Box<dyn StdError>> {
+pub fn memo_index(buf: &[u8]) -> Result<u32, Box<dyn StdError>> {
     if buf.len() == 4 {
         Ok(u32::from_le_bytes(buf.try_into()?))
     } else {
@@ -70,13 +112,63 @@ fn parse_logic(buf: &[u8]) -> FieldValue {
     }
 }
 
-fn parse_character(buf: &[u8]) -> FieldValue {
-    FieldValue::Character(String::from_utf8_lossy(buf).trim().to_owned())
+fn parse_character(buf: &[u8], encoding: Encoding) -> FieldValue {
+    FieldValue::Character(encoding.decode(buf).trim().to_owned())
+}
+
+// a set length bit means the field is shorter than its length, which is then stored in its last
+// byte
+fn parse_variable_length(
+    field: &FieldInfo,
+    buf: &[u8],
+    is_short: bool,
+    encoding: Encoding,
+) -> FieldValue {
+    let buf = match buf.split_last() {
+        Some((length, value)) if is_short => &value[..(*length as usize).min(value.len())],
+        _ => buf,
+    };
+
+    match field.field_type {
+        FieldType::Varbinary => FieldValue::Binary(buf.to_vec()),
+        _ => FieldValue::Character(encoding.decode(buf).into_owned()),
+    }
+}
+
+fn parse_integer(buf: &[u8], version: Version) -> Result<FieldValue, Box<dyn StdError>> {
+    let bytes = buf.try_into()?;
+    let value = match version {
+        Version::VisualFoxPro => i32::from_le_bytes(bytes),
+        // dBase 7 flips the sign bit so that the bytes sort like the values
+        Version::DBase7 => (u32::from_be_bytes(bytes) ^ 0x8000_0000) as i32,
+        _ => i32::from_be_bytes(bytes),
+    };
+    Ok(FieldValue::Integer(value as i64))
+}
+
+fn parse_currency(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
+    let value = i64::from_le_bytes(buf.try_into()?);
+    Ok(FieldValue::Currency(value))
 }
 
-fn parse_integer(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
-    let value = i32::from_be_bytes(buf.try_into()?);
-    Ok(FieldValue::Integer(value))
+fn parse_double(buf: &[u8], version: Version) -> Result<FieldValue, Box<dyn StdError>> {
+    let value = match version {
+        Version::DBase7 => dbase7_double(buf)?,
+        _ => f64::from_le_bytes(buf.try_into()?),
+    };
+    Ok(FieldValue::Float(value))
+}
+
+// dBase 7 stores doubles big-endian with the sign bit flipped for positive values and all bits
+// inverted for negative ones, so that the bytes sort like the values
+fn dbase7_double(buf: &[u8]) -> Result<f64, Box<dyn StdError>> {
+    let bits = u64::from_be_bytes(buf.try_into()?);
+    let bits = if bits & (1 << 63) != 0 {
+        bits ^ (1 << 63)
+    } else {
+        !bits
+    };
+    Ok(f64::from_bits(bits))
 }
 
 fn parse_numeric(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
@@ -88,6 +180,48 @@ fn parse_numeric(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
         .map_err(From::from)
 }
 
+// read from the digits, falling back to a float without fractional part (e.g. "12.0")
+fn parse_integral(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
+    let value = String::from_utf8_lossy(buf);
+    let text = value.trim();
+    match text.parse::<i64>() {
+        Ok(value) => Ok(FieldValue::Integer(value)),
+        Err(e) => match text.parse::<f64>() {
+            Ok(value) if value.fract() == 0.0 && value.abs() < i64::MAX as f64 => {
+                Ok(FieldValue::Integer(value as i64))
+            }
+            _ => Err(e.into()),
+        },
+    }
+}
+
+// scaled integer read from the digits, so no precision is lost through a float
+pub fn parse_decimal(buf: &[u8], scale: usize) -> Result<i128, ParseDecimalError> {
+    let value = String::from_utf8_lossy(buf);
+    let error = || ParseDecimalError(value.trim().to_owned());
+    let text = value.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty() && fraction.is_empty()
+        || !digits(whole)
+        || !digits(fraction)
+        || fraction.len() > scale
+    {
+        return Err(error());
+    }
+
+    let units = format!("{}{:0<scale$}", whole, fraction, scale = scale)
+        .parse::<i128>()
+        .map_err(|_| error())?;
+
+    Ok(if negative { -units } else { units })
+}
+
 fn parse_float(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
     let value = String::from_utf8_lossy(buf);
     value
@@ -154,13 +288,41 @@ fn parse_timestamp(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
     let (hour, minute, second, millisecond) = from_time_part_to_time(time_part);
 
     Ok(FieldValue::Timestamp(
-        year, month, day, hour, minute, second, millisecond
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        millisecond,
+    ))
+}
+
+// dBase 7 timestamps are the milliseconds since the start of the julian day count
+fn parse_dbase7_timestamp(buf: &[u8]) -> Result<FieldValue, Box<dyn StdError>> {
+    // rounded before splitting, so a time that rounds up to midnight moves to the next day
+    let milliseconds = dbase7_double(buf)?.round() as i64;
+    let date_part = milliseconds.div_euclid(MILLISECONDS_PER_DAY);
+    let time_part = milliseconds.rem_euclid(MILLISECONDS_PER_DAY);
+
+    let (year, month, day) = from_julian_day_to_gregorian_calender(date_part as u32);
+    let (hour, minute, second, millisecond) = from_time_part_to_time(time_part as u32);
+
+    Ok(FieldValue::Timestamp(
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        millisecond,
     ))
 }
 
 fn parse_memo<R>(
     buf: &[u8],
     memo_reader: &mut Option<MemoReader<R>>,
+    encoding: Encoding,
 ) -> Result<FieldValue, Box<dyn StdError>>
 where
     R: Read + Seek,
@@ -168,9 +330,7 @@ where
     if let Some(reader) = memo_reader.as_mut() {
         let index = memo_index(buf)?;
         let value = reader.read_memo(index)?;
-        Ok(FieldValue::Memo(
-            String::from_utf8_lossy(&value).into_owned(),
-        ))
+        Ok(FieldValue::Memo(encoding.decode(&value).into_owned()))
     } else {
         Ok(FieldValue::Null)
     }
@@ -207,3 +367,186 @@ where
         Ok(FieldValue::Null)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    fn parse(field: FieldInfo, version: Version, bytes: &[u8]) -> FieldValue {
+        let mut record = vec![b' '];
+        record.extend_from_slice(bytes);
+        let field = FieldInfo { offset: 1, ..field };
+        let mut memo_reader: Option<MemoReader<Cursor<Vec<u8>>>> = None;
+        parse_field(
+            &field,
+            None,
+            &record,
+            &mut memo_reader,
+            version,
+            Encoding::Utf8,
+        )
+        .unwrap()
+    }
+
+    fn field(field_type: FieldType, length: usize, decimal_count: usize) -> FieldInfo {
+        FieldInfo::with_type("FIELD", field_type, length, decimal_count)
+    }
+
+    fn dbase7_double(value: f64) -> [u8; 8] {
+        let bits = value.to_bits();
+        let bits = if value.is_sign_negative() {
+            !bits
+        } else {
+            bits ^ (1 << 63)
+        };
+        bits.to_be_bytes()
+    }
+
+    #[test]
+    fn visual_foxpro_integer_is_little_endian() {
+        let value = parse(
+            field(FieldType::Integer, 4, 0),
+            Version::VisualFoxPro,
+            &(-123_456i32).to_le_bytes(),
+        );
+        assert!(matches!(value, FieldValue::Integer(-123_456)));
+
+        // zeroed integers are zero, not null, as Visual FoxPro marks nulls in _NullFlags
+        let value = parse(
+            field(FieldType::Integer, 4, 0),
+            Version::VisualFoxPro,
+            &[0; 4],
+        );
+        assert!(matches!(value, FieldValue::Integer(0)));
+    }
+
+    #[test]
+    fn dbase3_integer_is_big_endian() {
+        let value = parse(
+            field(FieldType::Integer, 4, 0),
+            Version::DBase3,
+            &(-123_456i32).to_be_bytes(),
+        );
+        assert!(matches!(value, FieldValue::Integer(-123_456)));
+    }
+
+    #[test]
+    fn dbase7_integer_has_sign_bit_flipped() {
+        let value = parse(
+            field(FieldType::Integer, 4, 0),
+            Version::DBase7,
+            &[0x80, 0x00, 0x00, 0x2a],
+        );
+        assert!(matches!(value, FieldValue::Integer(42)));
+
+        let value = parse(
+            field(FieldType::Integer, 4, 0),
+            Version::DBase7,
+            &[0x7f, 0xff, 0xff, 0xfe],
+        );
+        assert!(matches!(value, FieldValue::Integer(-2)));
+    }
+
+    #[test]
+    fn dbase7_double_is_sortable_big_endian() {
+        for expected in &[1.5, -1.5, 0.0, -1234.25] {
+            match parse(
+                field(FieldType::Double, 8, 0),
+                Version::DBase7,
+                &dbase7_double(*expected),
+            ) {
+                FieldValue::Float(value) => assert_eq!(value, *expected),
+                value => panic!("unexpected value {:?}", value),
+            }
+        }
+    }
+
+    #[test]
+    fn dbase7_timestamp_carries_into_next_day() {
+        let milliseconds = 2_451_544.0 * MILLISECONDS_PER_DAY as f64 + 86_399_999.6;
+        let value = parse(
+            field(FieldType::Timestamp, 8, 0),
+            Version::DBase7,
+            &dbase7_double(milliseconds),
+        );
+        assert!(matches!(
+            value,
+            FieldValue::Timestamp(2000, 1, 1, 0, 0, 0, 0)
+        ));
+    }
+
+    #[test]
+    fn dbase3_numeric() {
+        let value = parse(
+            field(FieldType::Numeric, 8, 2),
+            Version::DBase3,
+            b" -12.50 ",
+        );
+        assert!(matches!(value, FieldValue::Numeric(v) if v == -12.5));
+
+        let value = parse(
+            field(FieldType::Numeric, 18, 0),
+            Version::DBase3,
+            b"123456789012345678",
+        );
+        assert!(matches!(
+            value,
+            FieldValue::Integer(123_456_789_012_345_678)
+        ));
+
+        let value = parse(
+            field(FieldType::Numeric, 8, 0),
+            Version::DBase3,
+            b"        ",
+        );
+        assert!(matches!(value, FieldValue::Null));
+    }
+
+    #[test]
+    fn visual_foxpro_currency_and_timestamp() {
+        let value = parse(
+            field(FieldType::Currency, 8, 4),
+            Version::VisualFoxPro,
+            &123_456i64.to_le_bytes(),
+        );
+        assert!(matches!(value, FieldValue::Currency(123_456)));
+
+        let mut bytes = 2_460_370u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&(((13 * 60 + 45) * 60 + 30) * 1000 + 250u32).to_le_bytes());
+        let value = parse(
+            field(FieldType::Timestamp, 8, 0),
+            Version::VisualFoxPro,
+            &bytes,
+        );
+        assert!(matches!(
+            value,
+            FieldValue::Timestamp(2024, 2, 29, 13, 45, 30, 250)
+        ));
+    }
+
+    #[test]
+    fn visual_foxpro_double_is_inline() {
+        let double = FieldType::from_code(b'B', Version::VisualFoxPro).unwrap();
+        assert_eq!(double, FieldType::Double);
+        let binary = FieldType::from_code(b'B', Version::DBase3).unwrap();
+        assert_eq!(binary, FieldType::Binary);
+
+        let value = parse(
+            field(FieldType::Double, 8, 0),
+            Version::VisualFoxPro,
+            &(-1.25f64).to_le_bytes(),
+        );
+        assert!(matches!(value, FieldValue::Float(v) if v == -1.25));
+    }
+
+    #[test]
+    fn decimal_from_digits() {
+        assert_eq!(parse_decimal(b"  1234.5", 2).unwrap(), 123_450);
+        assert_eq!(parse_decimal(b"-0.07", 2).unwrap(), -7);
+        assert_eq!(parse_decimal(b"12", 3).unwrap(), 12_000);
+        assert!(parse_decimal(b"1.234", 2).is_err());
+        assert!(parse_decimal(b" . ", 2).is_err());
+    }
+}
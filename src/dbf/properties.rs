@@ -0,0 +1,110 @@
+use std::convert::TryInto;
+
+use serde::Serialize;
+
+use super::field::FieldInfo;
+
+const STANDARD_DESCRIPTOR_LENGTH: usize = 15;
+const CUSTOM_DESCRIPTOR_LENGTH: usize = 14;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PropertyKind {
+    Required,
+    Minimum,
+    Maximum,
+    Default,
+    Constraint,
+    Custom(String),
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldProperty {
+    pub field: Option<String>,
+    pub kind: PropertyKind,
+    pub value: Vec<u8>,
+}
+
+// http://www.dbase.com/Knowledgebase/INT/db7_file_fmt.htm, "Field Properties Structure"
+pub fn read_field_properties(buf: &[u8], fields: &[FieldInfo]) -> Vec<FieldProperty> {
+    let read_u16 = |at: usize| -> Option<usize> {
+        let bytes = buf.get(at..at + 2)?.try_into().ok()?;
+        Some(u16::from_le_bytes(bytes) as usize)
+    };
+    let read_u32 = |at: usize| -> Option<usize> {
+        let bytes = buf.get(at..at + 4)?.try_into().ok()?;
+        Some(u32::from_le_bytes(bytes) as usize)
+    };
+    let slice = |start: usize, length: usize| buf.get(start..start + length);
+    let field_name = |index: usize| {
+        index
+            .checked_sub(1)
+            .and_then(|i| fields.get(i))
+            .map(|f| f.name.clone())
+    };
+
+    let mut properties = Vec::new();
+
+    let standard_count = read_u16(0).unwrap_or(0);
+    let standard_start = read_u16(2).unwrap_or(0);
+    for i in 0..standard_count {
+        let descriptor = standard_start + i * STANDARD_DESCRIPTOR_LENGTH;
+        let (field, kind, data_offset, data_length) = match (
+            read_u16(descriptor + 2),
+            buf.get(descriptor + 4),
+            read_u32(descriptor + 7),
+            read_u16(descriptor + 11),
+        ) {
+            (Some(field), Some(kind), Some(offset), Some(length)) => (field, *kind, offset, length),
+            _ => break,
+        };
+
+        let kind = match kind {
+            0x01 => PropertyKind::Required,
+            0x02 => PropertyKind::Minimum,
+            0x03 => PropertyKind::Maximum,
+            0x04 => PropertyKind::Default,
+            0x06 => PropertyKind::Constraint,
+            _ => continue,
+        };
+        // constraints are null terminated expressions, other values are stored like the field
+        let value = slice(data_offset, data_length).unwrap_or_default();
+        let value = match kind {
+            PropertyKind::Required => Vec::new(),
+            PropertyKind::Constraint => value.iter().copied().take_while(|b| *b != 0).collect(),
+            _ => value.to_vec(),
+        };
+
+        properties.push(FieldProperty {
+            field: field_name(field),
+            kind,
+            value,
+        });
+    }
+
+    let custom_count = read_u16(4).unwrap_or(0);
+    let custom_start = read_u16(6).unwrap_or(0);
+    for i in 0..custom_count {
+        let descriptor = custom_start + i * CUSTOM_DESCRIPTOR_LENGTH;
+        let (field, name, value) = match (
+            read_u16(descriptor + 2),
+            read_u16(descriptor + 6).zip(read_u16(descriptor + 8)),
+            read_u16(descriptor + 10).zip(read_u16(descriptor + 12)),
+        ) {
+            (Some(field), Some(name), Some(value)) => (field, name, value),
+            _ => break,
+        };
+
+        let name = slice(name.0, name.1).map(|n| String::from_utf8_lossy(n).into_owned());
+        let value = slice(value.0, value.1).map(<[u8]>::to_vec);
+        if let (Some(name), Some(value)) = (name, value) {
+            properties.push(FieldProperty {
+                field: field_name(field),
+                kind: PropertyKind::Custom(name),
+                value,
+            });
+        }
+    }
+
+    properties
+}
@@ -1,29 +1,61 @@
 use std::error::Error;
 use std::io::{Read, Seek, SeekFrom};
 
-use super::field::{read_field_info, FieldInfo, FieldValue};
+use super::encoding::Encoding;
+use super::field::{field_descriptor_length, read_field_info, FieldInfo, FieldValue, NullFlags};
 use super::header::Header;
 use super::memo::MemoReader;
 use super::parser;
+use super::properties::{read_field_properties, FieldProperty};
+use super::version::Version;
+use crate::error::{FieldBoundsError, FieldParseError};
 
 pub struct DbfReader<R: Read + Seek> {
     reader: R,
     header: Header,
     fields: Vec<FieldInfo>,
+    null_flags: Option<NullFlags>,
+    properties: Vec<FieldProperty>,
     memo_reader: Option<MemoReader<R>>,
     buffer: Vec<u8>,
+    record_number: u32,
+    include_deleted: bool,
+    encoding: Encoding,
 }
 
-impl<'a, R: Read + Seek> DbfReader<R> {
+impl<R: Read + Seek> DbfReader<R> {
     pub fn from_reader(mut reader: R, memo_reader: Option<R>) -> Result<Self, Box<dyn Error>> {
         let header = Header::from_reader(&mut reader)?;
 
-        reader.seek(SeekFrom::Start(32))?;
+        let descriptors_offset = header.field_descriptors_offset();
+        reader.seek(SeekFrom::Start(descriptors_offset as u64))?;
 
-        let fields = {
-            let mut buf = vec![0u8; header.header_length - 32];
-            reader.read_exact(&mut buf)?;
-            read_field_info(&buf)?
+        let mut buf = vec![0u8; header.header_length.saturating_sub(descriptors_offset)];
+        reader.read_exact(&mut buf)?;
+        let (fields, null_flags) = read_field_info(&buf, header.version)?;
+
+        // fields reaching past the end of the record cannot be read at all
+        let field_ends = fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.offset + f.length));
+        let null_flags_end = null_flags.map(|n| ("_NullFlags", n.end()));
+        if let Some((name, end)) = field_ends
+            .chain(null_flags_end)
+            .find(|(_, end)| *end > header.record_length)
+        {
+            return Err(Box::new(FieldBoundsError {
+                field_name: name.to_owned(),
+                end,
+                record_length: header.record_length,
+            }));
+        }
+
+        // dBase 7 keeps the field properties after the descriptor terminator
+        let properties = if header.version == Version::DBase7 {
+            let start = fields.len() * field_descriptor_length(header.version) + 1;
+            read_field_properties(buf.get(start..).unwrap_or_default(), &fields)
+        } else {
+            Vec::new()
         };
 
         let memo_reader = memo_reader
@@ -31,13 +63,20 @@ impl<'a, R: Read + Seek> DbfReader<R> {
             .transpose()?;
 
         let buffer = vec![0u8; header.record_length];
+        // text is decoded for the code page of the header, or as UTF-8 when it has none
+        let encoding = Encoding::for_code_page(header.code_page).unwrap_or_default();
 
         Ok(Self {
             reader,
             header,
             fields,
+            null_flags,
+            properties,
             memo_reader,
             buffer,
+            record_number: 0,
+            include_deleted: false,
+            encoding,
         })
     }
 
@@ -53,6 +92,30 @@ impl<'a, R: Read + Seek> DbfReader<R> {
         &self.fields
     }
 
+    pub fn null_flags(&self) -> Option<&NullFlags> {
+        self.null_flags.as_ref()
+    }
+
+    pub fn properties(&self) -> &[FieldProperty] {
+        &self.properties
+    }
+
+    pub fn memo_reader(&self) -> Option<&MemoReader<R>> {
+        self.memo_reader.as_ref()
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    pub fn set_include_deleted(&mut self, include_deleted: bool) {
+        self.include_deleted = include_deleted;
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+
     fn read_record(&mut self) -> Result<Option<()>, std::io::Error> {
         loop {
             self.buffer.resize(self.header.record_length, 0);
@@ -62,20 +125,242 @@ impl<'a, R: Read + Seek> DbfReader<R> {
                 return Ok(None);
             }
 
+            self.record_number += 1;
+
             let delete_mark = self.buffer[0] as char;
-            if delete_mark != '*' {
+            if self.include_deleted || delete_mark != '*' {
                 return Ok(Some(()));
             }
         }
     }
 
+    pub fn next_raw_record(&mut self) -> Result<Option<RawRecord<'_, R>>, std::io::Error> {
+        if self.read_record()?.is_none() {
+            return Ok(None);
+        }
+
+        Ok(Some(RawRecord {
+            fields: &self.fields,
+            null_flags: self.null_flags,
+            buffer: &self.buffer,
+            memo_reader: &mut self.memo_reader,
+            record_number: self.record_number,
+            version: self.header.version,
+            encoding: self.encoding,
+        }))
+    }
+
     pub fn next_record(&mut self) -> Result<Option<Vec<FieldValue>>, Box<dyn std::error::Error>> {
         if self.read_record()?.is_none() {
             return Ok(None);
         }
 
-        parser::parse_record(&self.fields, &self.buffer, &mut self.memo_reader)
+        parser::parse_record(
+            &self.fields,
+            self.null_flags,
+            &self.buffer,
+            &mut self.memo_reader,
+            self.header.version,
+            self.encoding,
+        )
+        .map(Some)
+        .map_err(From::from)
+    }
+}
+
+pub struct RawRecord<'a, R: Read + Seek> {
+    fields: &'a [FieldInfo],
+    null_flags: Option<NullFlags>,
+    buffer: &'a [u8],
+    memo_reader: &'a mut Option<MemoReader<R>>,
+    record_number: u32,
+    version: Version,
+    encoding: Encoding,
+}
+
+impl<'a, R: Read + Seek> RawRecord<'a, R> {
+    pub fn fields(&self) -> &[FieldInfo] {
+        self.fields
+    }
+
+    pub fn record_number(&self) -> u32 {
+        self.record_number
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.buffer[0] == b'*'
+    }
+
+    pub fn memo_block(&self, index: usize) -> Option<u32> {
+        let field = &self.fields[index];
+        if !field.field_type.is_memo() {
+            return None;
+        }
+
+        let buf = &self.buffer[field.offset..field.offset + field.length];
+        parser::memo_index(buf).ok().filter(|block| *block != 0)
+    }
+
+    pub fn parse_field(&mut self, index: usize) -> Result<FieldValue, FieldParseError> {
+        parser::parse_field(
+            &self.fields[index],
+            self.null_flags,
+            self.buffer,
+            self.memo_reader,
+            self.version,
+            self.encoding,
+        )
+    }
+
+    pub fn parse_decimal(&mut self, index: usize) -> Result<Option<i128>, FieldParseError> {
+        if let FieldValue::Null = self.parse_field(index)? {
+            return Ok(None);
+        }
+
+        let field = &self.fields[index];
+        let buf = &self.buffer[field.offset..field.offset + field.length];
+        parser::parse_decimal(buf, field.decimal_count)
             .map(Some)
-            .map_err(From::from)
+            .map_err(|e| {
+                FieldParseError::new(field.name.clone(), field.field_type.clone(), Some(e.into()))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    const VFP_BACKLINK_LENGTH: usize = 263;
+
+    // fields are (name, type, length, flags); the length is written over the length and decimal
+    // count bytes, like the extended character lengths
+    fn table(version: u8, fields: &[(&str, u8, usize, u8)], records: &[Vec<u8>]) -> Vec<u8> {
+        let backlink = if version == 0x30 {
+            VFP_BACKLINK_LENGTH
+        } else {
+            0
+        };
+        let header_length = 32 + 32 * fields.len() + 1 + backlink;
+        let record_length = 1 + fields.iter().map(|f| f.2).sum::<usize>();
+
+        let mut buf = vec![version, 124, 1, 1];
+        buf.extend_from_slice(&(records.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&(header_length as u16).to_le_bytes());
+        buf.extend_from_slice(&(record_length as u16).to_le_bytes());
+        buf.resize(32, 0);
+        for (name, field_type, length, flags) in fields {
+            let mut descriptor = name.as_bytes().to_vec();
+            descriptor.resize(11, 0);
+            descriptor.extend_from_slice(&[*field_type, 0, 0, 0, 0]);
+            descriptor.extend_from_slice(&(*length as u16).to_le_bytes());
+            descriptor.push(*flags);
+            descriptor.resize(32, 0);
+            buf.extend_from_slice(&descriptor);
+        }
+        buf.push(b'\r');
+        buf.resize(header_length, 0);
+        for record in records {
+            assert_eq!(record.len(), record_length);
+            buf.extend_from_slice(record);
+        }
+        buf.push(0x1a);
+        buf
+    }
+
+    fn read(buf: Vec<u8>) -> (Vec<FieldInfo>, Vec<Vec<FieldValue>>) {
+        let mut reader = DbfReader::from_reader(Cursor::new(buf), None).unwrap();
+        let mut records = Vec::new();
+        while let Some(record) = reader.next_record().unwrap() {
+            records.push(record);
+        }
+        (reader.fields().clone(), records)
+    }
+
+    fn record(parts: &[&[u8]]) -> Vec<u8> {
+        let mut record = vec![b' '];
+        parts.iter().for_each(|part| record.extend_from_slice(part));
+        record
+    }
+
+    #[test]
+    fn visual_foxpro_null_flags() {
+        // null bits: NAME 0, QTY 1, NOTE 3; length bits: NOTE 2, DATA 4
+        let fields = [
+            ("NAME", b'C', 5, 0x02),
+            ("QTY", b'I', 4, 0x02),
+            ("NOTE", b'V', 10, 0x02),
+            ("DATA", b'Q', 6, 0x04),
+            ("_NullFlags", b'0', 1, 0x05),
+        ];
+        let records = [
+            record(&[
+                b"ab   ",
+                &7i32.to_le_bytes(),
+                b"hello\0\0\0\0\x05",
+                &[1, 2, 3, 0, 0, 3],
+                &[0b1_0100],
+            ]),
+            record(&[
+                b"     ",
+                &[0; 4],
+                &[0; 10],
+                &[1, 2, 3, 4, 5, 6],
+                &[0b0_1011],
+            ]),
+            record(&[b"     ", &[0; 4], b"abcdefghij", &[0; 6], &[0]]),
+        ];
+
+        let (fields, records) = read(table(0x30, &fields, &records));
+
+        assert_eq!(fields.len(), 4);
+        assert!(fields[0].flags.nullable && !fields[3].flags.nullable);
+
+        assert!(matches!(&records[0][0], FieldValue::Character(v) if v == "ab"));
+        assert!(matches!(records[0][1], FieldValue::Integer(7)));
+        assert!(matches!(&records[0][2], FieldValue::Character(v) if v == "hello"));
+        assert!(matches!(&records[0][3], FieldValue::Binary(v) if v == &[1, 2, 3]));
+
+        assert!(matches!(records[1][0], FieldValue::Null));
+        assert!(matches!(records[1][1], FieldValue::Null));
+        assert!(matches!(records[1][2], FieldValue::Null));
+        assert!(matches!(&records[1][3], FieldValue::Binary(v) if v == &[1, 2, 3, 4, 5, 6]));
+
+        assert!(matches!(&records[2][0], FieldValue::Character(v) if v.is_empty()));
+        assert!(matches!(records[2][1], FieldValue::Integer(0)));
+        assert!(matches!(&records[2][2], FieldValue::Character(v) if v == "abcdefghij"));
+        assert!(matches!(&records[2][3], FieldValue::Binary(v) if v == &[0; 6]));
+    }
+
+    #[test]
+    fn extended_character_length() {
+        let text = "x".repeat(399) + "y";
+        let fields = [("TEXT", b'C', 400, 0), ("QTY", b'N', 3, 0)];
+        let records = [record(&[text.as_bytes(), b" 42"])];
+
+        let (fields, records) = read(table(0x03, &fields, &records));
+
+        assert_eq!(fields[0].length, 400);
+        assert_eq!((fields[1].offset, fields[1].length), (401, 3));
+        assert!(matches!(&records[0][0], FieldValue::Character(v) if *v == text));
+        assert!(matches!(records[0][1], FieldValue::Integer(42)));
+    }
+
+    #[test]
+    fn fields_past_the_end_of_the_record() {
+        let fields = [("NAME", b'C', 5, 0), ("QTY", b'N', 3, 0)];
+        let mut buf = table(0x03, &fields, &[record(&[b"ab   ", b" 42"])]);
+        // the record length no longer covers QTY
+        buf[10..12].copy_from_slice(&7u16.to_le_bytes());
+
+        let error = DbfReader::from_reader(Cursor::new(buf), None)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Field 'QTY' ends at byte 9 but records are 7 bytes long"
+        );
     }
 }
@@ -1,12 +1,15 @@
 use std::convert::TryFrom;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+use serde::Serialize;
+
+#[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum Version {
     FoxBase,
     DBase3,
     DBase4,
     VisualFoxPro,
     FoxPro2,
+    DBase7,
 }
 
 impl TryFrom<u8> for Version {
@@ -16,12 +19,25 @@ impl TryFrom<u8> for Version {
         let value = match value {
             0b0000_0010 => Version::FoxBase,
             0b0000_0011 | 0b1000_0011 => Version::DBase3,
-            0b0011_0000 | 0b0011_0001 | 0b0011_0010 => Version::VisualFoxPro,
+            0b0000_0100 | 0b1000_1100 => Version::DBase7,
+            0b0011_0000..=0b0011_0010 => Version::VisualFoxPro,
             0b0100_0011 | 0b0110_0011 | 0b1000_1011 | 0b1100_1011 => Version::DBase4,
             0b1111_0101 | 0b1111_1011 => Version::FoxPro2,
-            _ => return Err("Unknown version".to_owned()),
+            _ => return Err(format!("Unknown version 0x{:02x}", value)),
         };
 
         Ok(value)
     }
 }
+
+impl From<Version> for u8 {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::FoxBase => 0b0000_0010,
+            // the dBase IV and FoxPro 2 signatures announce a memo file, which is never written
+            Version::DBase3 | Version::DBase4 | Version::FoxPro2 => 0b0000_0011,
+            Version::VisualFoxPro => 0b0011_0000,
+            Version::DBase7 => 0b0000_0100,
+        }
+    }
+}
@@ -0,0 +1,617 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use byteorder::{BigEndian, LittleEndian, WriteBytesExt};
+
+use super::encoding::Encoding;
+use super::field::{
+    has_extended_character_length, read_field_info, Autoincrement, FieldFlags, FieldInfo,
+    FieldType, AUTOINCREMENT_FLAGS, NULL_FLAGS_TYPE,
+};
+use super::header::Header;
+use super::version::Version;
+use crate::error::{
+    FieldWriteError, RecordLengthError, TableLengthError, UnsupportedFieldTypeError,
+    UnsupportedVersionError,
+};
+use crate::model::{Currency, Date};
+use crate::value::Value;
+
+const FIELD_DESCRIPTOR_LENGTH: usize = 32;
+const MAX_FIELD_NAME_LENGTH: usize = 10;
+const VFP_BACKLINK_LENGTH: usize = 263;
+const JULIAN_DAY_OF_EPOCH: i64 = 2_440_588;
+const HEADER_LENGTH: u64 = 32;
+const AUTOINCREMENT_NEXT_OFFSET: u64 = 19;
+
+pub struct DbfWriter<W: Write + Seek> {
+    writer: W,
+    version: Version,
+    fields: Vec<FieldInfo>,
+    record_length: usize,
+    record_count: u32,
+    encoding: Encoding,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write + Seek> DbfWriter<W> {
+    pub fn new(
+        mut writer: W,
+        version: Version,
+        code_page: u8,
+        mut fields: Vec<FieldInfo>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if version == Version::DBase7 {
+            return Err(UnsupportedVersionError(version).into());
+        }
+
+        let mut offset = 1;
+        for field in fields.iter_mut() {
+            check_field(field, version)?;
+            field.offset = offset;
+            offset += field.length;
+        }
+
+        let record_length = offset;
+        let mut header_length = 32 + fields.len() * FIELD_DESCRIPTOR_LENGTH + 1;
+        if version == Version::VisualFoxPro {
+            header_length += VFP_BACKLINK_LENGTH;
+        }
+        if header_length > u16::MAX as usize {
+            return Err(TableLengthError::Header(header_length).into());
+        }
+        if record_length > u16::MAX as usize {
+            return Err(TableLengthError::Record(record_length).into());
+        }
+
+        // Visual FoxPro tables with autoincrement fields have their own signature
+        let signature = match version {
+            Version::VisualFoxPro if fields.iter().any(|f| f.autoincrement.is_some()) => 0x31,
+            version => u8::from(version),
+        };
+        writer.write_u8(signature)?;
+        write_last_update(&mut writer)?;
+        writer.write_u32::<LittleEndian>(0)?;
+        writer.write_u16::<LittleEndian>(header_length as u16)?;
+        writer.write_u16::<LittleEndian>(record_length as u16)?;
+        writer.write_all(&[0u8; 17])?;
+        writer.write_u8(code_page)?;
+        writer.write_all(&[0u8; 2])?;
+
+        for field in fields.iter() {
+            let mut name = [0u8; 11];
+            let name_bytes = field.name.as_bytes();
+            let name_length = name_bytes.len().min(MAX_FIELD_NAME_LENGTH);
+            name[..name_length].copy_from_slice(&name_bytes[..name_length]);
+
+            writer.write_all(&name)?;
+            writer.write_u8(field.field_type.code())?;
+            writer.write_u32::<LittleEndian>(field.offset as u32)?;
+            if field.field_type == FieldType::Character {
+                writer.write_u16::<LittleEndian>(field.length as u16)?;
+            } else {
+                writer.write_u8(field.length as u8)?;
+                writer.write_u8(field.decimal_count as u8)?;
+            }
+            // only the binary flag is kept, nullable and system fields need a _NullFlags column
+            let flags = match version {
+                Version::VisualFoxPro => FieldFlags {
+                    binary: field.flags.binary,
+                    ..Default::default()
+                }
+                .bits(),
+                _ => 0,
+            };
+            match field.autoincrement {
+                Some(autoincrement) if version == Version::VisualFoxPro => {
+                    writer.write_u8(flags | AUTOINCREMENT_FLAGS)?;
+                    writer.write_i32::<LittleEndian>(autoincrement.next)?;
+                    writer.write_u8(autoincrement.step as u8)?;
+                    writer.write_all(&[0u8; 8])?;
+                }
+                _ => {
+                    writer.write_u8(flags)?;
+                    writer.write_all(&[0u8; 13])?;
+                }
+            }
+        }
+
+        writer.write_u8(b'\r')?;
+        if version == Version::VisualFoxPro {
+            writer.write_all(&[0u8; VFP_BACKLINK_LENGTH])?;
+        }
+
+        Ok(Self {
+            writer,
+            version,
+            fields,
+            record_length,
+            record_count: 0,
+            encoding: Encoding::for_code_page(code_page).unwrap_or_default(),
+            buffer: vec![b' '; record_length],
+        })
+    }
+}
+
+impl<W: Read + Write + Seek> DbfWriter<W> {
+    // autoincrement fields continue from the next value stored in the field descriptors
+    pub fn append(mut writer: W) -> Result<Self, Box<dyn Error>> {
+        let header = Header::from_reader(&mut writer)?;
+        if header.version == Version::DBase7 {
+            return Err(UnsupportedVersionError(header.version).into());
+        }
+
+        let descriptors_offset = header.field_descriptors_offset();
+        let mut buf = vec![0u8; header.header_length.saturating_sub(descriptors_offset)];
+        writer.seek(SeekFrom::Start(descriptors_offset as u64))?;
+        writer.read_exact(&mut buf)?;
+        let (fields, null_flags) = read_field_info(&buf, header.version)?;
+        if null_flags.is_some() {
+            return Err(UnsupportedFieldTypeError(NULL_FLAGS_TYPE as char).into());
+        }
+        for field in fields.iter() {
+            check_field(field, header.version)?;
+        }
+
+        writer.seek(SeekFrom::Start(1))?;
+        write_last_update(&mut writer)?;
+        // the end of file marker is overwritten by the new records
+        let end = header.header_length + header.record_count as usize * header.record_length;
+        writer.seek(SeekFrom::Start(end as u64))?;
+
+        Ok(Self {
+            writer,
+            version: header.version,
+            fields,
+            record_length: header.record_length,
+            record_count: header.record_count,
+            encoding: Encoding::for_code_page(header.code_page).unwrap_or_default(),
+            buffer: vec![b' '; header.record_length],
+        })
+    }
+}
+
+impl<W: Write + Seek> DbfWriter<W> {
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn record_count(&self) -> u32 {
+        self.record_count
+    }
+
+    // text is encoded for the code page of the header, or as UTF-8 when it has none
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+
+    pub fn write_record(&mut self, values: &[Value]) -> Result<(), Box<dyn Error>> {
+        if values.len() != self.fields.len() {
+            return Err(RecordLengthError {
+                expected: self.fields.len(),
+                found: values.len(),
+            }
+            .into());
+        }
+
+        self.buffer.clear();
+        self.buffer.resize(self.record_length, b' ');
+
+        for (field, value) in self.fields.iter_mut().zip(values) {
+            let buf = &mut self.buffer[field.offset..field.offset + field.length];
+            match (field.autoincrement, value) {
+                (Some(autoincrement), Value::Null) => {
+                    encode_field(
+                        field,
+                        &Value::Int(i64::from(autoincrement.next)),
+                        self.version,
+                        self.encoding,
+                        buf,
+                    )?;
+                    field.autoincrement = Some(Autoincrement {
+                        next: autoincrement.next.wrapping_add(autoincrement.step),
+                        ..autoincrement
+                    });
+                }
+                _ => encode_field(field, value, self.version, self.encoding, buf)?,
+            }
+        }
+
+        self.writer.write_all(&self.buffer)?;
+        self.record_count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, std::io::Error> {
+        self.writer.write_u8(0x1a)?;
+        self.writer.seek(SeekFrom::Start(4))?;
+        self.writer.write_u32::<LittleEndian>(self.record_count)?;
+        if self.version == Version::VisualFoxPro {
+            for (index, field) in self.fields.iter().enumerate() {
+                if let Some(autoincrement) = field.autoincrement {
+                    let position = HEADER_LENGTH
+                        + (index * FIELD_DESCRIPTOR_LENGTH) as u64
+                        + AUTOINCREMENT_NEXT_OFFSET;
+                    self.writer.seek(SeekFrom::Start(position))?;
+                    self.writer.write_i32::<LittleEndian>(autoincrement.next)?;
+                }
+            }
+        }
+        self.writer.seek(SeekFrom::End(0))?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn check_field(field: &FieldInfo, version: Version) -> Result<(), Box<dyn Error>> {
+    let unsupported_double =
+        field.field_type == FieldType::Double && version != Version::VisualFoxPro;
+    if field.field_type.is_memo() || field.field_type.is_variable_length() || unsupported_double {
+        return Err(UnsupportedFieldTypeError(field.type_code() as char).into());
+    }
+
+    let max_length = match field.field_type {
+        FieldType::Character if has_extended_character_length(version) => u16::MAX as usize,
+        _ => u8::MAX as usize,
+    };
+    if field.length > max_length {
+        return Err(FieldWriteError::new(
+            field.name.clone(),
+            field.field_type.clone(),
+            format!("fields are limited to {} bytes", max_length),
+        )
+        .into());
+    }
+
+    // Visual FoxPro keeps the step in a single byte
+    let step = field.autoincrement.map(|a| a.step);
+    if version == Version::VisualFoxPro && step.is_some_and(|step| u8::try_from(step).is_err()) {
+        return Err(FieldWriteError::new(
+            field.name.clone(),
+            field.field_type.clone(),
+            "autoincrement steps are limited to 0-255",
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+fn write_last_update<W: Write>(writer: &mut W) -> std::io::Result<()> {
+    let today = Date::today();
+    writer.write_u8((today.year - 1900) as u8)?;
+    writer.write_u8(today.month)?;
+    writer.write_u8(today.day)
+}
+
+fn encode_text(
+    field: &FieldInfo,
+    text: &str,
+    encoding: Encoding,
+    buf: &mut [u8],
+) -> Result<(), FieldWriteError> {
+    let bytes = encoding.encode(text).ok_or_else(|| {
+        FieldWriteError::new(
+            field.name.clone(),
+            field.field_type.clone(),
+            "value has characters the encoding of the table cannot represent",
+        )
+    })?;
+    if bytes.len() > buf.len() {
+        return Err(FieldWriteError::new(
+            field.name.clone(),
+            field.field_type.clone(),
+            format!("value is longer than {} bytes", buf.len()),
+        ));
+    }
+
+    buf[..bytes.len()].copy_from_slice(&bytes);
+    Ok(())
+}
+
+fn encode_number(field: &FieldInfo, value: f64, buf: &mut [u8]) -> Result<(), FieldWriteError> {
+    let text = format!("{:>1$.2$}", value, field.length, field.decimal_count);
+    encode_text(field, &text, Encoding::Utf8, buf)
+}
+
+// integers are formatted exactly, without going through f64
+fn encode_integer(field: &FieldInfo, value: i64, buf: &mut [u8]) -> Result<(), FieldWriteError> {
+    let text = match field.decimal_count {
+        0 => value.to_string(),
+        decimal_count => format!("{}.{}", value, "0".repeat(decimal_count)),
+    };
+    encode_text(
+        field,
+        &format!("{:>1$}", text, field.length),
+        Encoding::Utf8,
+        buf,
+    )
+}
+
+fn encode_currency(value: Currency, mut buf: &mut [u8]) -> std::io::Result<()> {
+    buf.write_i64::<LittleEndian>(value.units)
+}
+
+fn encode_double(value: f64, mut buf: &mut [u8]) -> std::io::Result<()> {
+    buf.write_f64::<LittleEndian>(value)
+}
+
+fn encode_field(
+    field: &FieldInfo,
+    value: &Value,
+    version: Version,
+    encoding: Encoding,
+    buf: &mut [u8],
+) -> Result<(), FieldWriteError> {
+    let mismatch = || {
+        FieldWriteError::new(
+            field.name.clone(),
+            field.field_type.clone(),
+            "value does not match the field type",
+        )
+    };
+
+    match (&field.field_type, value) {
+        (FieldType::Integer, Value::Null)
+        | (FieldType::Currency, Value::Null)
+        | (FieldType::Double, Value::Null)
+        | (FieldType::Timestamp, Value::Null) => {
+            buf.iter_mut().for_each(|b| *b = 0);
+            Ok(())
+        }
+        (_, Value::Null) => Ok(()),
+        (FieldType::Character, Value::Str(val)) => encode_text(field, val, encoding, buf),
+        (FieldType::Character, Value::Int(val)) => {
+            encode_text(field, &val.to_string(), encoding, buf)
+        }
+        (FieldType::Character, Value::Float(val)) => {
+            encode_text(field, &val.to_string(), encoding, buf)
+        }
+        (FieldType::Character, Value::Date(val)) => {
+            encode_text(field, &val.to_string(), encoding, buf)
+        }
+        (FieldType::Character, Value::Timestamp(val)) => {
+            encode_text(field, &val.to_string(), encoding, buf)
+        }
+        (FieldType::Logical, Value::Bool(val)) => {
+            buf[0] = if *val { b'T' } else { b'F' };
+            Ok(())
+        }
+        (FieldType::Numeric, Value::Int(val)) | (FieldType::Float, Value::Int(val)) => {
+            encode_integer(field, *val, buf)
+        }
+        (FieldType::Numeric, Value::Float(val)) | (FieldType::Float, Value::Float(val)) => {
+            encode_number(field, *val, buf)
+        }
+        (FieldType::Date, Value::Date(val)) => {
+            encode_text(field, &val.format("%Y%m%d"), encoding, buf)
+        }
+        (FieldType::Integer, Value::Int(val)) if buf.len() == 4 => {
+            let val = i32::try_from(*val).map_err(|_| mismatch())?;
+            let mut buf = buf;
+            match version {
+                Version::VisualFoxPro => buf.write_i32::<LittleEndian>(val),
+                _ => buf.write_i32::<BigEndian>(val),
+            }
+            .map_err(|_| mismatch())
+        }
+        (FieldType::Currency, Value::Currency(val)) if buf.len() == 8 => {
+            encode_currency(*val, buf).map_err(|_| mismatch())
+        }
+        (FieldType::Currency, Value::Int(val)) if buf.len() == 8 => {
+            let units = val.checked_mul(Currency::SCALE).ok_or_else(mismatch)?;
+            encode_currency(Currency::new(units), buf).map_err(|_| mismatch())
+        }
+        (FieldType::Currency, Value::Float(val)) if buf.len() == 8 => {
+            let units = (val * Currency::SCALE as f64).round();
+            if !units.is_finite() || units.abs() >= i64::MAX as f64 {
+                return Err(mismatch());
+            }
+            encode_currency(Currency::new(units as i64), buf).map_err(|_| mismatch())
+        }
+        (FieldType::Double, Value::Float(val)) if buf.len() == 8 => {
+            encode_double(*val, buf).map_err(|_| mismatch())
+        }
+        (FieldType::Double, Value::Int(val)) if buf.len() == 8 => {
+            encode_double(*val as f64, buf).map_err(|_| mismatch())
+        }
+        (FieldType::Double, Value::Currency(val)) if buf.len() == 8 => {
+            encode_double(val.to_f64(), buf).map_err(|_| mismatch())
+        }
+        (FieldType::Character, Value::Currency(val)) => {
+            encode_text(field, &val.to_string(), encoding, buf)
+        }
+        (FieldType::Numeric, Value::Currency(val)) | (FieldType::Float, Value::Currency(val)) => {
+            encode_number(field, val.to_f64(), buf)
+        }
+        (FieldType::Timestamp, Value::Timestamp(val)) if buf.len() == 8 => {
+            let julian_day = val.date.days_since_epoch() as i64 + JULIAN_DAY_OF_EPOCH;
+            let mut buf = buf;
+            buf.write_u32::<LittleEndian>(julian_day as u32)
+                .and_then(|_| {
+                    buf.write_u32::<LittleEndian>(val.time.milliseconds_since_midnight() as u32)
+                })
+                .map_err(|_| mismatch())
+        }
+        _ => Err(mismatch()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::DbfReader;
+    use crate::model::Timestamp;
+    use crate::record::DynamicRecordIterator;
+
+    fn write(
+        version: Version,
+        code_page: u8,
+        fields: Vec<FieldInfo>,
+        records: &[Vec<Value>],
+    ) -> Vec<u8> {
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), version, code_page, fields).unwrap();
+        for values in records {
+            writer.write_record(values).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn read(table: Vec<u8>) -> Vec<Vec<Value>> {
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+        DynamicRecordIterator::new(reader)
+            .map(|r| r.unwrap().into_values())
+            .collect()
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 5, 0),
+            FieldInfo::with_type("PRICE", FieldType::Numeric, 8, 2),
+            FieldInfo::with_type("ACTIVE", FieldType::Logical, 1, 0),
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+            FieldInfo::with_type("COUNT", FieldType::Integer, 4, 0),
+            FieldInfo::with_type("AMOUNT", FieldType::Currency, 8, 4),
+            FieldInfo::with_type("RATIO", FieldType::Double, 8, 0),
+            FieldInfo::with_type("UPDATED", FieldType::Timestamp, 8, 0),
+        ];
+        let records = vec![
+            vec![
+                Value::Str("widget".to_owned()),
+                Value::Int(42),
+                Value::Float(12.5),
+                Value::Bool(true),
+                Value::Date(Date::new(2024, 2, 29)),
+                Value::Int(-7),
+                Value::Currency("1234.5678".parse().unwrap()),
+                Value::Float(0.25),
+                Value::Timestamp(Timestamp::new(2024, 2, 29, 13, 45, 30, 250)),
+            ],
+            vec![
+                Value::Str(String::new()),
+                Value::Null,
+                Value::Null,
+                Value::Bool(false),
+                Value::Null,
+                Value::Int(0),
+                Value::Currency(Currency::new(0)),
+                Value::Float(0.0),
+                Value::Null,
+            ],
+        ];
+
+        let table = write(Version::VisualFoxPro, 0, fields, &records);
+
+        assert_eq!(table[0], 0x30);
+        assert_eq!(read(table), records);
+    }
+
+    #[test]
+    fn tables_without_memo_have_dbase3_signature() {
+        let fields = vec![FieldInfo::with_type("NAME", FieldType::Character, 10, 0)];
+        for version in &[Version::DBase3, Version::DBase4, Version::FoxPro2] {
+            let table = write(*version, 0, fields.clone(), &[]);
+            assert_eq!(table[0], 0x03);
+        }
+    }
+
+    #[test]
+    fn text_without_code_page_is_utf8() {
+        let fields = vec![FieldInfo::with_type("CITY", FieldType::Character, 12, 0)];
+        let records = vec![vec![Value::Str("São Paulo".to_owned())]];
+
+        let table = write(Version::DBase3, 0, fields, &records);
+
+        assert_eq!(read(table), records);
+    }
+
+    #[cfg(feature = "encoding")]
+    #[test]
+    fn text_is_encoded_for_the_code_page() {
+        let fields = vec![FieldInfo::with_type("CITY", FieldType::Character, 12, 0)];
+        let records = vec![vec![Value::Str("São Paulo".to_owned())]];
+
+        // 0x03 is Windows ANSI (cp1252)
+        let table = write(Version::DBase3, 0x03, fields.clone(), &records);
+        let offset = 32 + 32 + 1 + 1;
+        assert_eq!(&table[offset..offset + 9], b"S\xe3o Paulo");
+        assert_eq!(read(table), records);
+
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0x03, fields).unwrap();
+        let error = writer
+            .write_record(&[Value::Str("東京".to_owned())])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Field 'CITY' (Character) could not be written: value has characters the encoding of the table cannot represent"
+        );
+    }
+
+    #[test]
+    fn record_with_missing_values_is_rejected() {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("QTY", FieldType::Integer, 4, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::VisualFoxPro, 0, fields).unwrap();
+
+        let error = writer
+            .write_record(&[Value::Str("a".to_owned())])
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Record has 1 values but the table has 2 fields"
+        );
+        assert_eq!(writer.record_count(), 0);
+    }
+
+    #[test]
+    fn autoincrement_fields() {
+        let mut id = FieldInfo::with_type("ID", FieldType::Integer, 4, 0);
+        id.autoincrement = Some(Autoincrement { next: 10, step: 5 });
+        let fields = vec![
+            id,
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+        ];
+        let record = |id: Value, name: &str| vec![id, Value::Str(name.to_owned())];
+        let records = vec![
+            record(Value::Null, "a"),
+            record(Value::Int(3), "b"),
+            record(Value::Null, "c"),
+        ];
+
+        let table = write(Version::VisualFoxPro, 0, fields, &records);
+        assert_eq!(table[0], 0x31);
+
+        // appending continues from the next value stored in the header
+        let mut writer = DbfWriter::append(Cursor::new(table)).unwrap();
+        let autoincrement = writer.fields()[0].autoincrement.unwrap();
+        assert_eq!((autoincrement.next, autoincrement.step), (20, 5));
+        writer.write_record(&record(Value::Null, "d")).unwrap();
+        let table = writer.finish().unwrap().into_inner();
+
+        let reader = DbfReader::from_reader(Cursor::new(table.clone()), None).unwrap();
+        assert_eq!(reader.fields()[0].autoincrement.unwrap().next, 25);
+        let ids = read(table)
+            .into_iter()
+            .map(|r| r[0].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                Value::Int(10),
+                Value::Int(3),
+                Value::Int(15),
+                Value::Int(20)
+            ]
+        );
+    }
+}
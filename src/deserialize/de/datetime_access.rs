@@ -48,7 +48,15 @@ pub struct TimestampAccess {
 }
 
 impl TimestampAccess {
-    pub fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, millisecond: u16) -> Self {
+    pub fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        millisecond: u16,
+    ) -> Self {
         Self {
             year: Some(year),
             month: Some(month),
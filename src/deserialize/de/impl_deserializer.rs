@@ -4,6 +4,7 @@ use super::datetime_access::{DateAccess, TimestampAccess};
 use crate::dbf::{FieldType, FieldValue};
 use crate::deserialize::DbfDeserializer;
 use crate::error::DeserializeError;
+use crate::model::Currency;
 
 impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
     type Error = DeserializeError;
@@ -17,17 +18,25 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
             return visitor.visit_none();
         }
 
+        if self.peek_field_info().is_some_and(|f| f.is_integral()) {
+            return self.deserialize_i64(visitor);
+        }
+
         match self.peek_field() {
             Some(FieldType::Logical) => self.deserialize_bool(visitor),
             Some(FieldType::Character) => self.deserialize_string(visitor),
+            Some(FieldType::Currency) => self.deserialize_string(visitor),
             Some(FieldType::Integer) => self.deserialize_i64(visitor),
             Some(FieldType::Numeric) => self.deserialize_f64(visitor),
             Some(FieldType::Float) => self.deserialize_f64(visitor),
+            Some(FieldType::Double) => self.deserialize_f64(visitor),
             Some(FieldType::Date) => self.deserialize_seq(visitor),
             Some(FieldType::Timestamp) => self.deserialize_seq(visitor),
             Some(FieldType::Memo) => self.deserialize_string(visitor),
             Some(FieldType::Binary) => self.deserialize_byte_buf(visitor),
             Some(FieldType::General) => self.deserialize_byte_buf(visitor),
+            Some(FieldType::Varchar) => self.deserialize_string(visitor),
+            Some(FieldType::Varbinary) => self.deserialize_byte_buf(visitor),
             None => Err(DeserializeError::unexpected_end_of_record()),
         }
     }
@@ -70,11 +79,15 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
         V: Visitor<'de>,
     {
         match self.next_value() {
-            Some(FieldValue::Integer(value)) => visitor.visit_i32(value),
+            Some(FieldValue::Integer(value)) => visitor.visit_i64(value),
             Some(FieldValue::Numeric(value)) if value.fract() != 0.0 => {
                 Err(self.error_field_parse())
             }
             Some(FieldValue::Numeric(value)) => visitor.visit_i64(value.trunc() as i64),
+            Some(FieldValue::Currency(value)) if value % Currency::SCALE != 0 => {
+                Err(self.error_field_parse())
+            }
+            Some(FieldValue::Currency(value)) => visitor.visit_i64(value / Currency::SCALE),
             Some(FieldValue::Null) => Err(self.error_unexpected_null()),
             Some(_) => Err(self.error_expected(FieldType::Integer)),
             None => Err(self.error_end_of_record()),
@@ -108,12 +121,18 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
     {
         match self.next_value() {
             Some(FieldValue::Integer(value)) if value < 0 => Err(self.error_field_parse()),
-            Some(FieldValue::Integer(value)) => visitor.visit_u32(value as u32),
+            Some(FieldValue::Integer(value)) => visitor.visit_u64(value as u64),
             Some(FieldValue::Numeric(value)) if value < 0.0 => Err(self.error_field_parse()),
             Some(FieldValue::Numeric(value)) if value.fract() != 0.0 => {
                 Err(self.error_field_parse())
             }
             Some(FieldValue::Numeric(value)) => visitor.visit_u64(value.trunc() as u64),
+            Some(FieldValue::Currency(value)) if value < 0 || value % Currency::SCALE != 0 => {
+                Err(self.error_field_parse())
+            }
+            Some(FieldValue::Currency(value)) => {
+                visitor.visit_u64((value / Currency::SCALE) as u64)
+            }
             Some(FieldValue::Null) => Err(self.error_unexpected_null()),
             Some(_) => Err(self.error_expected(FieldType::Integer)),
             None => Err(self.error_end_of_record()),
@@ -135,6 +154,8 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
             Some(FieldValue::Float(value)) | Some(FieldValue::Numeric(value)) => {
                 visitor.visit_f64(value)
             }
+            Some(FieldValue::Integer(value)) => visitor.visit_f64(value as f64),
+            Some(FieldValue::Currency(value)) => visitor.visit_f64(Currency::new(value).to_f64()),
             Some(FieldValue::Null) => Err(self.error_unexpected_null()),
             Some(_) => Err(self.error_expected(FieldType::Float)),
             None => Err(self.error_end_of_record()),
@@ -169,6 +190,9 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
             Some(FieldValue::Character(value)) | Some(FieldValue::Memo(value)) => {
                 visitor.visit_string(value)
             }
+            Some(FieldValue::Currency(value)) => {
+                visitor.visit_string(Currency::new(value).to_string())
+            }
             Some(FieldValue::Null) => Err(self.error_unexpected_null()),
             Some(_) => Err(self.error_expected(FieldType::Character)),
             None => Err(self.error_end_of_record()),
@@ -251,7 +275,15 @@ impl<'a, 'de: 'a> Deserializer<'de> for &'a mut DbfDeserializer {
                 visitor.visit_seq(DateAccess::new(year, month, day))
             }
             Some(FieldValue::Timestamp(year, month, day, hour, minute, second, millisecond)) => {
-                visitor.visit_seq(TimestampAccess::new(year, month, day, hour, minute, second, millisecond))
+                visitor.visit_seq(TimestampAccess::new(
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    millisecond,
+                ))
             }
             Some(_) => Err(self.error_expected(FieldType::Date)),
             None => Err(self.error_unexpected_null()),
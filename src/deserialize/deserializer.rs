@@ -31,19 +31,16 @@ impl DbfDeserializer {
     }
 
     pub fn set_values(&mut self, values: Vec<FieldValue>) {
-        self.values = self
-            .fields
-            .clone()
-            .into_iter()
-            .zip(values.into_iter())
-            .collect();
+        self.values = self.fields.clone().into_iter().zip(values).collect();
         self.record_count += 1;
     }
 
     pub fn peek_field(&self) -> Option<&FieldType> {
-        self.fields
-            .get(self.fields.len() - self.values.len())
-            .map(|f| &f.field_type)
+        self.peek_field_info().map(|f| &f.field_type)
+    }
+
+    pub fn peek_field_info(&self) -> Option<&FieldInfo> {
+        self.fields.get(self.fields.len() - self.values.len())
     }
 
     pub fn next_value(&mut self) -> Option<FieldValue> {
@@ -51,10 +48,9 @@ impl DbfDeserializer {
     }
 
     pub fn is_next_value_null(&self) -> Option<bool> {
-        self.values.get(0).map(|(_, v)| match v {
-            FieldValue::Null => true,
-            _ => false,
-        })
+        self.values
+            .front()
+            .map(|(_, v)| matches!(v, FieldValue::Null))
     }
 
     pub fn has_next_field(&self) -> bool {
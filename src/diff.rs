@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+
+use serde::Serialize;
+
+use crate::dbf::FieldInfo;
+use crate::record::{DynamicRecordIterator, Record};
+use crate::value::Value;
+
+#[derive(Clone, Debug, Serialize)]
+pub struct FieldChange {
+    pub before: FieldInfo,
+    pub after: FieldInfo,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct SchemaDiff {
+    pub added: Vec<FieldInfo>,
+    pub removed: Vec<FieldInfo>,
+    pub changed: Vec<FieldChange>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ValueChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RecordChange {
+    pub key: Vec<Value>,
+    pub changes: Vec<ValueChange>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TableDiff {
+    #[serde(skip)]
+    pub fields: Vec<FieldInfo>,
+    pub schema: SchemaDiff,
+    pub inserted: Vec<Record>,
+    pub deleted: Vec<Record>,
+    pub changed: Vec<RecordChange>,
+}
+
+fn find_field<'a>(fields: &'a [FieldInfo], name: &str) -> Option<&'a FieldInfo> {
+    fields.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+}
+
+fn diff_schema(old: &[FieldInfo], new: &[FieldInfo]) -> SchemaDiff {
+    let mut schema = SchemaDiff::default();
+
+    for field in new {
+        match find_field(old, &field.name) {
+            None => schema.added.push(field.clone()),
+            Some(before)
+                if before.field_type != field.field_type
+                    || before.length != field.length
+                    || before.decimal_count != field.decimal_count =>
+            {
+                schema.changed.push(FieldChange {
+                    before: before.clone(),
+                    after: field.clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    schema.removed = old
+        .iter()
+        .filter(|f| find_field(new, &f.name).is_none())
+        .cloned()
+        .collect();
+
+    schema
+}
+
+// values are compared as trimmed text and numbers by value, so 1 and 1.0 are equal keys and a
+// field changing from N(5,0) to N(5,2) does not change every record
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Normalized {
+    Null,
+    Text(String),
+    Number(String),
+    Other(String),
+}
+
+impl From<&Value> for Normalized {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => Normalized::Null,
+            Value::Str(val) => Normalized::Text(val.trim().to_owned()),
+            Value::Int(val) => Normalized::Number(val.to_string()),
+            Value::Float(val) if *val == 0.0 => Normalized::Number("0".to_owned()),
+            Value::Float(val) => Normalized::Number(val.to_string()),
+            Value::Currency(val) => {
+                let text = val.to_string();
+                Normalized::Number(text.trim_end_matches('0').trim_end_matches('.').to_owned())
+            }
+            value => Normalized::Other(format!("{:?}", value)),
+        }
+    }
+}
+
+struct Snapshot {
+    fields: Vec<FieldInfo>,
+    records: Vec<(Vec<Normalized>, Record)>,
+    index: HashMap<Vec<Normalized>, usize>,
+}
+
+impl Snapshot {
+    fn read<R: Read + Seek>(
+        records: DynamicRecordIterator<R>,
+        key: &[String],
+        table: &str,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let fields = records.fields().to_vec();
+
+        for name in key {
+            if find_field(&fields, name).is_none() {
+                return Err(format!("key field '{}' does not exist in the {}", name, table).into());
+            }
+        }
+
+        let mut keyed = Vec::new();
+        let mut index = HashMap::new();
+        for record in records {
+            let record = record?;
+            let record_key = key_values(&record, key)
+                .iter()
+                .map(Normalized::from)
+                .collect::<Vec<_>>();
+
+            if index.insert(record_key.clone(), keyed.len()).is_some() {
+                return Err(format!(
+                    "record {} of the {} repeats the key of a previous record",
+                    record.record_number(),
+                    table
+                )
+                .into());
+            }
+            keyed.push((record_key, record));
+        }
+
+        Ok(Self {
+            fields,
+            records: keyed,
+            index,
+        })
+    }
+}
+
+fn key_values(record: &Record, key: &[String]) -> Vec<Value> {
+    key.iter()
+        .map(|name| record.get_by_name(name).cloned().unwrap_or(Value::Null))
+        .collect()
+}
+
+impl TableDiff {
+    pub fn from_records<O, N>(
+        old: DynamicRecordIterator<O>,
+        new: DynamicRecordIterator<N>,
+        key: &[String],
+    ) -> Result<Self, Box<dyn std::error::Error>>
+    where
+        O: Read + Seek,
+        N: Read + Seek,
+    {
+        let old = Snapshot::read(old, key, "old table")?;
+        let new = Snapshot::read(new, key, "new table")?;
+
+        let schema = diff_schema(&old.fields, &new.fields);
+        let common_fields = new
+            .fields
+            .iter()
+            .filter(|f| find_field(&old.fields, &f.name).is_some())
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+
+        let mut inserted = Vec::new();
+        let mut changed = Vec::new();
+        for (record_key, record) in new.records.iter() {
+            let old_record = match old.index.get(record_key) {
+                Some(i) => &old.records[*i].1,
+                None => {
+                    inserted.push(record.clone());
+                    continue;
+                }
+            };
+
+            let changes = common_fields
+                .iter()
+                .filter_map(|name| {
+                    let before = old_record.get_by_name(name)?;
+                    let after = record.get_by_name(name)?;
+                    if Normalized::from(before) == Normalized::from(after) {
+                        return None;
+                    }
+                    Some(ValueChange {
+                        field: name.clone(),
+                        before: before.clone(),
+                        after: after.clone(),
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            if !changes.is_empty() {
+                changed.push(RecordChange {
+                    key: key_values(record, key),
+                    changes,
+                });
+            }
+        }
+
+        let deleted = old
+            .records
+            .iter()
+            .filter(|(record_key, _)| !new.index.contains_key(record_key))
+            .map(|(_, record)| record.clone())
+            .collect();
+
+        Ok(Self {
+            fields: new.fields,
+            schema,
+            inserted,
+            deleted,
+            changed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfReader, DbfWriter, FieldType, Version};
+
+    fn table(
+        fields: Vec<FieldInfo>,
+        records: &[Vec<Value>],
+    ) -> DynamicRecordIterator<Cursor<Vec<u8>>> {
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        for values in records {
+            writer.write_record(values).unwrap();
+        }
+        let mut table = writer.finish().unwrap();
+        table.set_position(0);
+        DynamicRecordIterator::new(DbfReader::from_reader(table, None).unwrap())
+    }
+
+    fn fields(decimal_count: usize) -> Vec<FieldInfo> {
+        vec![
+            FieldInfo::with_type("ID", FieldType::Character, 4, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 5, decimal_count),
+        ]
+    }
+
+    fn row(id: &str, qty: i64) -> Vec<Value> {
+        vec![Value::Str(id.to_owned()), Value::Int(qty)]
+    }
+
+    fn key() -> Vec<String> {
+        vec!["id".to_owned()]
+    }
+
+    #[test]
+    fn inserted_deleted_and_changed_records() {
+        let old = table(fields(0), &[row("a", 1), row("b", 2), row("c", 3)]);
+        let new = table(fields(0), &[row("a", 1), row("c", 4), row("d", 5)]);
+
+        let diff = TableDiff::from_records(old, new, &key()).unwrap();
+
+        assert_eq!(diff.inserted.len(), 1);
+        assert_eq!(diff.inserted[0]["ID"], Value::Str("d".to_owned()));
+        assert_eq!(diff.deleted.len(), 1);
+        assert_eq!(diff.deleted[0]["ID"], Value::Str("b".to_owned()));
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].key, vec![Value::Str("c".to_owned())]);
+        assert_eq!(diff.changed[0].changes.len(), 1);
+        assert_eq!(diff.changed[0].changes[0].field, "QTY");
+        assert_eq!(diff.changed[0].changes[0].before, Value::Int(3));
+        assert_eq!(diff.changed[0].changes[0].after, Value::Int(4));
+    }
+
+    #[test]
+    fn values_are_compared_normalized() {
+        // QTY changes from N(5,0) to N(5,2), so its values change from Int to Float
+        let old = table(fields(0), &[row("a", 1), row("b", 2)]);
+        let new = table(fields(2), &[row("a", 1), row("b", 3)]);
+
+        let diff = TableDiff::from_records(old, new, &key()).unwrap();
+
+        assert_eq!(diff.schema.changed.len(), 1);
+        assert_eq!(diff.schema.changed[0].after.decimal_count, 2);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].changes[0].before, Value::Int(2));
+        assert_eq!(diff.changed[0].changes[0].after, Value::Float(3.0));
+    }
+
+    #[test]
+    fn keys_are_matched_normalized() {
+        let numeric_key = || vec![FieldInfo::with_type("ID", FieldType::Numeric, 5, 0)];
+        let old = table(numeric_key(), &[vec![Value::Int(1)]]);
+        let new = table(
+            vec![FieldInfo::with_type("ID", FieldType::Numeric, 5, 1)],
+            &[vec![Value::Float(1.0)]],
+        );
+
+        let diff = TableDiff::from_records(old, new, &key()).unwrap();
+
+        assert!(diff.inserted.is_empty() && diff.deleted.is_empty() && diff.changed.is_empty());
+        assert_eq!(
+            Normalized::from(&Value::Str(" x ".to_owned())),
+            Normalized::from(&Value::Str("x".to_owned()))
+        );
+        assert_eq!(
+            Normalized::from(&Value::Currency("1.5".parse().unwrap())),
+            Normalized::from(&Value::Float(1.5))
+        );
+        assert_eq!(
+            Normalized::from(&Value::Float(-0.0)),
+            Normalized::from(&Value::Int(0))
+        );
+    }
+
+    #[test]
+    fn schema_changes() {
+        let mut new_fields = fields(0);
+        new_fields.remove(1);
+        new_fields.push(FieldInfo::with_type("NOTE", FieldType::Character, 10, 0));
+        let old = table(fields(0), &[]);
+        let new = table(new_fields, &[]);
+
+        let diff = TableDiff::from_records(old, new, &key()).unwrap();
+
+        let names =
+            |fields: &[FieldInfo]| fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&diff.schema.added), vec!["NOTE"]);
+        assert_eq!(names(&diff.schema.removed), vec!["QTY"]);
+        assert!(diff.schema.changed.is_empty());
+    }
+
+    #[test]
+    fn invalid_keys() {
+        let old = table(fields(0), &[row("a", 1)]);
+        let new = table(fields(0), &[row("a", 1)]);
+        let error = TableDiff::from_records(old, new, &["name".to_owned()]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "key field 'name' does not exist in the old table"
+        );
+
+        let old = table(fields(0), &[row("a", 1)]);
+        let new = table(fields(0), &[row("a", 1), row("a ", 2)]);
+        let error = TableDiff::from_records(old, new, &key()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "record 2 of the new table repeats the key of a previous record"
+        );
+    }
+}
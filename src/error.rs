@@ -1,10 +1,8 @@
 use std::error::Error as StdError;
-use std::fmt;
+use std::fmt::{self, Formatter};
 use std::io::Error as IoError;
 
-use serde::export::Formatter;
-
-use crate::dbf::FieldType;
+use crate::dbf::{FieldType, Version};
 
 #[derive(Debug)]
 pub struct UnsupportedFieldTypeError(pub char);
@@ -17,11 +15,22 @@ impl fmt::Display for UnsupportedFieldTypeError {
 
 impl StdError for UnsupportedFieldTypeError {}
 
+#[derive(Debug)]
+pub struct UnsupportedVersionError(pub Version);
+
+impl fmt::Display for UnsupportedVersionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Writing {:?} tables is not supported", self.0)
+    }
+}
+
+impl StdError for UnsupportedVersionError {}
+
 #[derive(Debug)]
 pub struct FieldParseError {
     field_name: String,
     field_type: FieldType,
-    source: Option<Box<(dyn StdError + 'static)>>,
+    source: Option<Box<dyn StdError + 'static>>,
 }
 
 impl FieldParseError {
@@ -65,6 +74,100 @@ impl StdError for FieldParseError {
     }
 }
 
+#[derive(Debug)]
+pub enum TableLengthError {
+    Header(usize),
+    Record(usize),
+}
+
+impl fmt::Display for TableLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        let (name, length) = match self {
+            TableLengthError::Header(length) => ("Header", length),
+            TableLengthError::Record(length) => ("Record", length),
+        };
+        write!(
+            f,
+            "{} length of {} bytes exceeds {}",
+            name,
+            length,
+            u16::MAX
+        )
+    }
+}
+
+impl StdError for TableLengthError {}
+
+#[derive(Debug)]
+pub struct RecordLengthError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for RecordLengthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Record has {} values but the table has {} fields",
+            self.found, self.expected
+        )
+    }
+}
+
+impl StdError for RecordLengthError {}
+
+#[derive(Debug)]
+pub struct FieldBoundsError {
+    pub field_name: String,
+    pub end: usize,
+    pub record_length: usize,
+}
+
+impl fmt::Display for FieldBoundsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Field '{}' ends at byte {} but records are {} bytes long",
+            self.field_name, self.end, self.record_length
+        )
+    }
+}
+
+impl StdError for FieldBoundsError {}
+
+#[derive(Debug)]
+pub struct FieldWriteError {
+    field_name: String,
+    field_type: FieldType,
+    reason: String,
+}
+
+impl FieldWriteError {
+    pub fn new<S, T>(field_name: S, field_type: FieldType, reason: T) -> Self
+    where
+        S: Into<String>,
+        T: Into<String>,
+    {
+        Self {
+            field_name: field_name.into(),
+            field_type,
+            reason: reason.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldWriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "Field '{}' ({}) could not be written: {}",
+            self.field_name, self.field_type, self.reason
+        )
+    }
+}
+
+impl StdError for FieldWriteError {}
+
 #[derive(Debug)]
 pub struct NoSuchFieldError {
     field: String,
@@ -86,6 +189,60 @@ impl fmt::Display for NoSuchFieldError {
 
 impl StdError for NoSuchFieldError {}
 
+#[derive(Debug)]
+pub struct BatchSizeError;
+
+impl fmt::Display for BatchSizeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Batch size must be greater than zero")
+    }
+}
+
+impl StdError for BatchSizeError {}
+
+#[derive(Debug)]
+pub struct ParseCurrencyError(pub String);
+
+impl fmt::Display for ParseCurrencyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "'{}' is not a valid currency value", self.0)
+    }
+}
+
+impl StdError for ParseCurrencyError {}
+
+#[derive(Debug)]
+pub struct ParseDecimalError(pub String);
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "'{}' is not a valid decimal value", self.0)
+    }
+}
+
+impl StdError for ParseDecimalError {}
+
+#[derive(Debug)]
+pub struct ExpressionError {
+    message: String,
+}
+
+impl ExpressionError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "Invalid expression: {}", self.message)
+    }
+}
+
+impl StdError for ExpressionError {}
+
 #[derive(Debug)]
 pub struct DeserializeError {
     code: ErrorCode,
@@ -165,9 +322,34 @@ pub enum ErrorCode {
     UnexpectedEndOfRecord,
 }
 
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorCode::Custom(message) => f.write_str(message),
+            ErrorCode::Io(e) => write!(f, "{}", e),
+            ErrorCode::Expected(field_type) => write!(f, "expected {} value", field_type),
+            ErrorCode::TupleLength(length, field_count) => write!(
+                f,
+                "tuple of length {} does not fit a record of {} fields",
+                length, field_count
+            ),
+            ErrorCode::UnexpectedNull => f.write_str("unexpected null value"),
+            ErrorCode::ExpectedNull => f.write_str("expected null value"),
+            ErrorCode::NoSuchField => f.write_str("no such field"),
+            ErrorCode::FieldParse => f.write_str("value could not be parsed"),
+            ErrorCode::MissingMemoFile => f.write_str("missing memo file"),
+            ErrorCode::UnexpectedEndOfRecord => f.write_str("unexpected end of record"),
+        }
+    }
+}
+
 impl fmt::Display for DeserializeError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Deserialize error at {}:{}", self.field, self.record)
+        write!(
+            f,
+            "Deserialize error at {}:{}: {}",
+            self.field, self.record, self.code
+        )
     }
 }
 
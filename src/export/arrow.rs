@@ -0,0 +1,281 @@
+use std::io::{Read, Seek};
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float64Array, Int32Array,
+    Int64Array, StringArray, TimestampMillisecondArray,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::error::ArrowError;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+
+use crate::batch::{Batch, BatchIterator, Column};
+use crate::dbf::{FieldInfo, FieldType};
+
+const MAX_DECIMAL_PRECISION: usize = 38;
+const CURRENCY_PRECISION: u8 = 19;
+const CURRENCY_SCALE: i8 = 4;
+
+pub fn arrow_data_type(field: &FieldInfo) -> DataType {
+    match field.field_type {
+        FieldType::Logical => DataType::Boolean,
+        FieldType::Character | FieldType::Varchar | FieldType::Memo => DataType::Utf8,
+        FieldType::Integer => DataType::Int32,
+        FieldType::Numeric if field.is_integral() => DataType::Int64,
+        FieldType::Numeric => DataType::Decimal128(
+            field.length.min(MAX_DECIMAL_PRECISION) as u8,
+            field.decimal_count as i8,
+        ),
+        FieldType::Float | FieldType::Double => DataType::Float64,
+        FieldType::Currency => DataType::Decimal128(CURRENCY_PRECISION, CURRENCY_SCALE),
+        FieldType::Date => DataType::Date32,
+        FieldType::Timestamp => DataType::Timestamp(TimeUnit::Millisecond, None),
+        FieldType::Binary | FieldType::General | FieldType::Varbinary => DataType::Binary,
+    }
+}
+
+pub fn arrow_schema(fields: &[FieldInfo]) -> Schema {
+    Schema::new(
+        fields
+            .iter()
+            .map(|f| Field::new(&f.name, arrow_data_type(f), true))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn to_array(column: &Column, data_type: &DataType) -> Result<ArrayRef, ArrowError> {
+    let array: ArrayRef = match (column, data_type) {
+        (Column::Logical(values), _) => Arc::new(BooleanArray::from(values.clone())),
+        (Column::Character(values), _) => {
+            Arc::new(values.iter().map(|v| v.as_deref()).collect::<StringArray>())
+        }
+        (Column::Integer(values), DataType::Int32) => Arc::new(
+            values
+                .iter()
+                .map(|v| v.map(|v| v as i32))
+                .collect::<Int32Array>(),
+        ),
+        (Column::Integer(values), _) => Arc::new(Int64Array::from(values.clone())),
+        (Column::Decimal(values), DataType::Decimal128(precision, scale)) => Arc::new(
+            values
+                .iter()
+                .copied()
+                .collect::<Decimal128Array>()
+                .with_precision_and_scale(*precision, *scale)?,
+        ),
+        (Column::Decimal(_), data_type) => {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Decimal column cannot be converted to {}",
+                data_type
+            )))
+        }
+        (Column::Float(values), _) => Arc::new(Float64Array::from(values.clone())),
+        (Column::Currency(values), _) => Arc::new(
+            values
+                .iter()
+                .map(|v| v.map(|v| v.units as i128))
+                .collect::<Decimal128Array>()
+                .with_precision_and_scale(CURRENCY_PRECISION, CURRENCY_SCALE)?,
+        ),
+        (Column::Date(values), _) => Arc::new(
+            values
+                .iter()
+                .map(|v| v.as_ref().map(|d| d.days_since_epoch()))
+                .collect::<Date32Array>(),
+        ),
+        (Column::Timestamp(values), _) => Arc::new(
+            values
+                .iter()
+                .map(|v| v.as_ref().map(|t| t.milliseconds_since_epoch()))
+                .collect::<TimestampMillisecondArray>(),
+        ),
+        (Column::Bytes(values), _) => {
+            Arc::new(values.iter().map(|v| v.as_deref()).collect::<BinaryArray>())
+        }
+    };
+
+    Ok(array)
+}
+
+pub fn to_record_batch(batch: &Batch, schema: SchemaRef) -> Result<RecordBatch, ArrowError> {
+    let columns = batch
+        .columns()
+        .iter()
+        .zip(schema.fields().iter())
+        .map(|(column, field)| to_array(column, field.data_type()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    RecordBatch::try_new_with_options(schema, columns, &options)
+}
+
+pub struct ArrowBatchIterator<R>
+where
+    R: Read + Seek,
+{
+    batches: BatchIterator<R>,
+    schema: SchemaRef,
+}
+
+impl<R: Read + Seek> ArrowBatchIterator<R> {
+    pub fn new(batches: BatchIterator<R>) -> Self {
+        let schema = Arc::new(arrow_schema(&batches.fields()));
+        Self { batches, schema }
+    }
+
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl<R: Read + Seek> Iterator for ArrowBatchIterator<R> {
+    type Item = Result<RecordBatch, Box<dyn std::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match self.batches.next()? {
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(e)),
+        };
+
+        Some(to_record_batch(&batch, self.schema.clone()).map_err(From::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use arrow::array::Array;
+
+    use super::*;
+    use crate::dbf::{DbfReader, DbfWriter, Version};
+    use crate::model::{Date, Timestamp};
+    use crate::value::Value;
+
+    #[test]
+    fn data_types() {
+        let data_type =
+            |field_type, length| arrow_data_type(&FieldInfo::with_type("F", field_type, length, 0));
+
+        assert_eq!(data_type(FieldType::Logical, 1), DataType::Boolean);
+        assert_eq!(data_type(FieldType::Character, 10), DataType::Utf8);
+        assert_eq!(data_type(FieldType::Memo, 10), DataType::Utf8);
+        assert_eq!(data_type(FieldType::Integer, 4), DataType::Int32);
+        assert_eq!(data_type(FieldType::Float, 10), DataType::Float64);
+        assert_eq!(data_type(FieldType::Double, 8), DataType::Float64);
+        assert_eq!(
+            data_type(FieldType::Currency, 8),
+            DataType::Decimal128(19, 4)
+        );
+        assert_eq!(data_type(FieldType::Date, 8), DataType::Date32);
+        assert_eq!(
+            data_type(FieldType::Timestamp, 8),
+            DataType::Timestamp(TimeUnit::Millisecond, None)
+        );
+        assert_eq!(data_type(FieldType::General, 10), DataType::Binary);
+    }
+
+    #[test]
+    fn record_batches() {
+        let fields = vec![
+            FieldInfo::with_type("PRICE", FieldType::Numeric, 8, 2),
+            FieldInfo::with_type("COUNT", FieldType::Integer, 4, 0),
+            FieldInfo::with_type("AMOUNT", FieldType::Currency, 8, 4),
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+            FieldInfo::with_type("UPDATED", FieldType::Timestamp, 8, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::VisualFoxPro, 0, fields).unwrap();
+        writer
+            .write_record(&[
+                Value::Float(12.5),
+                Value::Int(-7),
+                Value::Currency("1234.5678".parse().unwrap()),
+                Value::Date(Date::new(1970, 1, 2)),
+                Value::Timestamp(Timestamp::new(1970, 1, 1, 0, 0, 1, 500)),
+            ])
+            .unwrap();
+        writer
+            .write_record(&[
+                Value::Null,
+                Value::Int(0),
+                Value::Null,
+                Value::Null,
+                Value::Null,
+            ])
+            .unwrap();
+        let table = writer.finish().unwrap().into_inner();
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+
+        let mut batches = ArrowBatchIterator::new(BatchIterator::new(reader, 10).unwrap());
+        let batch = batches.next().unwrap().unwrap();
+
+        let price = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!((price.precision(), price.scale()), (8, 2));
+        assert_eq!((price.value(0), price.is_null(1)), (1250, true));
+        let count = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(count.values(), &[-7, 0]);
+        let amount = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Decimal128Array>()
+            .unwrap();
+        assert_eq!(amount.value(0), 12_345_678);
+        let since = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<Date32Array>()
+            .unwrap();
+        assert_eq!(since.value(0), 1);
+        let updated = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        assert_eq!(updated.value(0), 1500);
+        assert!(batches.next().is_none());
+    }
+
+    #[test]
+    fn decimal_precision_and_scale() {
+        let field = FieldInfo::with_type("N", FieldType::Numeric, 40, 2);
+        assert_eq!(arrow_data_type(&field), DataType::Decimal128(38, 2));
+
+        let column = Column::Decimal(vec![Some(1)]);
+        // a decimal count larger than the length is a scale larger than the precision
+        let error = to_array(&column, &DataType::Decimal128(2, 3)).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("scale 3 is greater than precision 2"));
+        let error = to_array(&column, &DataType::Float64).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument error: Decimal column cannot be converted to Float64"
+        );
+    }
+
+    #[test]
+    fn numeric_data_types() {
+        let data_type = |length, decimal_count| {
+            arrow_data_type(&FieldInfo::with_type(
+                "N",
+                FieldType::Numeric,
+                length,
+                decimal_count,
+            ))
+        };
+
+        assert_eq!(data_type(18, 0), DataType::Int64);
+        assert_eq!(data_type(19, 0), DataType::Decimal128(19, 0));
+        assert_eq!(data_type(20, 0), DataType::Decimal128(20, 0));
+        assert_eq!(data_type(10, 2), DataType::Decimal128(10, 2));
+    }
+}
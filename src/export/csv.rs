@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+use std::io::{Read, Seek, Write};
+
+use csv::WriterBuilder;
+
+use super::BinaryEncoding;
+use crate::dbf::{FieldInfo, FieldType};
+use crate::record::Record;
+use crate::value::Value;
+use crate::ValuesIterator;
+
+pub use csv::QuoteStyle;
+
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    pub delimiter: u8,
+    pub quote: u8,
+    pub quote_style: QuoteStyle,
+    pub date_format: String,
+    pub timestamp_format: String,
+    pub include_memo: bool,
+    pub binary_encoding: BinaryEncoding,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: b',',
+            quote: b'"',
+            quote_style: QuoteStyle::Necessary,
+            date_format: "%Y-%m-%d".to_owned(),
+            timestamp_format: "%Y-%m-%dT%H:%M:%S.%f".to_owned(),
+            include_memo: true,
+            binary_encoding: BinaryEncoding::Base64,
+        }
+    }
+}
+
+pub struct CsvWriter<W: Write> {
+    writer: csv::Writer<W>,
+    fields: Vec<FieldInfo>,
+    options: CsvOptions,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(writer: W, fields: &[FieldInfo], options: CsvOptions) -> Self {
+        let writer = WriterBuilder::new()
+            .delimiter(options.delimiter)
+            .quote(options.quote)
+            .quote_style(options.quote_style)
+            .from_writer(writer);
+
+        let fields = fields
+            .iter()
+            .filter(|f| options.include_memo || !f.field_type.is_memo())
+            .cloned()
+            .collect();
+
+        Self {
+            writer,
+            fields,
+            options,
+        }
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn write_header(&mut self) -> Result<(), csv::Error> {
+        self.writer
+            .write_record(self.fields.iter().map(|f| f.name.as_str()))
+    }
+
+    pub fn write_record(&mut self, record: &BTreeMap<String, Value>) -> Result<(), csv::Error> {
+        let row = self
+            .fields
+            .iter()
+            .map(|f| match record.get(&f.name) {
+                Some(value) => self.format_value(f, value),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        self.writer.write_record(&row)
+    }
+
+    // same as write_record, without collecting the values into a map first
+    pub fn write_row(&mut self, record: &Record) -> Result<(), csv::Error> {
+        let row = self
+            .fields
+            .iter()
+            .map(|f| match record.get_by_name(&f.name) {
+                Some(value) => self.format_value(f, value),
+                None => String::new(),
+            })
+            .collect::<Vec<_>>();
+
+        self.writer.write_record(&row)
+    }
+
+    pub fn flush(&mut self) -> Result<(), std::io::Error> {
+        self.writer.flush()
+    }
+
+    fn format_value(&self, field: &FieldInfo, value: &Value) -> String {
+        match value {
+            Value::Str(val) => val.clone(),
+            Value::Int(val) => val.to_string(),
+            Value::Float(val) if field.field_type == FieldType::Double => val.to_string(),
+            Value::Float(val) => format!("{:.*}", field.decimal_count, val),
+            Value::Currency(val) => val.to_string(),
+            Value::Bool(val) => val.to_string(),
+            Value::Date(val) => val.format(&self.options.date_format),
+            Value::Timestamp(val) => val.format(&self.options.timestamp_format),
+            Value::Bytes(val) => self.options.binary_encoding.encode(val),
+            Value::Null => String::new(),
+        }
+    }
+}
+
+pub fn write_csv<R, W>(
+    values: ValuesIterator<R>,
+    writer: W,
+    options: CsvOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    let mut writer = CsvWriter::new(writer, values.fields(), options);
+    writer.write_header()?;
+
+    let mut record_count = 0;
+    for record in values {
+        writer.write_record(&record?)?;
+        record_count += 1;
+    }
+
+    writer.flush()?;
+    Ok(record_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfReader, DbfWriter, Version};
+    use crate::model::{Date, Timestamp};
+
+    fn write(
+        fields: &[FieldInfo],
+        records: &[BTreeMap<String, Value>],
+        options: CsvOptions,
+    ) -> String {
+        let mut writer = CsvWriter::new(Vec::new(), fields, options);
+        writer.write_header().unwrap();
+        for record in records {
+            writer.write_record(record).unwrap();
+        }
+        String::from_utf8(writer.writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn columns_keep_the_order_of_the_table() {
+        let fields = vec![
+            FieldInfo::with_type("ZED", FieldType::Character, 5, 0),
+            FieldInfo::with_type("ALPHA", FieldType::Numeric, 6, 2),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        writer
+            .write_record(&[Value::Str("a, b".to_owned()), Value::Float(1.5)])
+            .unwrap();
+        writer.write_record(&[Value::Null, Value::Null]).unwrap();
+        let table = writer.finish().unwrap().into_inner();
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+
+        let mut output = Vec::new();
+        let record_count = write_csv(
+            crate::ValuesIterator { reader },
+            &mut output,
+            CsvOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(record_count, 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "ZED,ALPHA\n\"a, b\",1.50\n,\n"
+        );
+    }
+
+    #[test]
+    fn formatting_options() {
+        let fields = vec![
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+            FieldInfo::with_type("UPDATED", FieldType::Timestamp, 8, 0),
+            FieldInfo::with_type("DATA", FieldType::Varbinary, 10, 0),
+            FieldInfo::with_type("NOTE", FieldType::Memo, 10, 0),
+        ];
+        let record = vec![
+            ("SINCE".to_owned(), Value::Date(Date::new(2024, 2, 29))),
+            (
+                "UPDATED".to_owned(),
+                Value::Timestamp(Timestamp::new(2024, 2, 29, 13, 45, 30, 250)),
+            ),
+            ("DATA".to_owned(), Value::Bytes(vec![0xca, 0xfe])),
+            ("NOTE".to_owned(), Value::Str("note".to_owned())),
+        ]
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+
+        let options = CsvOptions {
+            delimiter: b';',
+            date_format: "%d/%m/%Y".to_owned(),
+            timestamp_format: "%Y-%m-%d %H:%M".to_owned(),
+            include_memo: false,
+            binary_encoding: BinaryEncoding::Hex,
+            ..Default::default()
+        };
+        let csv = write(&fields, std::slice::from_ref(&record), options);
+        assert_eq!(
+            csv,
+            "SINCE;UPDATED;DATA\n29/02/2024;2024-02-29 13:45;cafe\n"
+        );
+
+        let csv = write(&fields, &[record], CsvOptions::default());
+        assert_eq!(
+            csv,
+            "SINCE,UPDATED,DATA,NOTE\n2024-02-29,2024-02-29T13:45:30.250,yv4=,note\n"
+        );
+    }
+}
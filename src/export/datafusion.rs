@@ -0,0 +1,385 @@
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::catalog::{DynamicFileCatalog, Session, UrlTableFactory};
+use datafusion::datasource::{TableProvider, TableType};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::{SendableRecordBatchStream, SessionStateBuilder, TaskContext};
+use datafusion::logical_expr::Expr;
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::execution_plan::{Boundedness, EmissionType};
+use datafusion::physical_plan::stream::RecordBatchReceiverStreamBuilder;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, Partitioning, PlanProperties,
+};
+use datafusion::prelude::SessionContext;
+
+use super::arrow::{arrow_schema, to_record_batch};
+use crate::dbf::FieldInfo;
+use crate::error::BatchSizeError;
+
+#[derive(Debug)]
+pub struct DbfTable {
+    table_path: PathBuf,
+    memo_path: Option<PathBuf>,
+    fields: Vec<FieldInfo>,
+    schema: SchemaRef,
+    batch_size: usize,
+}
+
+fn external_error(e: Box<dyn std::error::Error>) -> DataFusionError {
+    DataFusionError::External(e.to_string().into())
+}
+
+fn table_schema(fields: &[FieldInfo], lowercase_names: bool) -> SchemaRef {
+    if !lowercase_names {
+        return Arc::new(arrow_schema(fields));
+    }
+
+    let fields = fields
+        .iter()
+        .cloned()
+        .map(|mut f| {
+            f.name = f.name.to_lowercase();
+            f
+        })
+        .collect::<Vec<_>>();
+    Arc::new(arrow_schema(&fields))
+}
+
+impl DbfTable {
+    pub fn try_new<P: AsRef<Path>>(table_path: P, memo_path: Option<P>) -> Result<Self> {
+        let table_path = table_path.as_ref().to_path_buf();
+        let memo_path = memo_path.map(|p| p.as_ref().to_path_buf());
+
+        let batches = crate::read_batches(&table_path, None, 1).map_err(external_error)?;
+        let fields = batches.fields();
+        // column names are lowercased to match the identifier normalization of DataFusion sql
+        let schema = table_schema(&fields, true);
+
+        Ok(Self {
+            table_path,
+            memo_path,
+            fields,
+            schema,
+            batch_size: 8192,
+        })
+    }
+
+    // with the names as stored in the table, upper case columns must be quoted in sql
+    pub fn with_lowercase_names(mut self, lowercase_names: bool) -> Self {
+        self.schema = table_schema(&self.fields, lowercase_names);
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self> {
+        if batch_size == 0 {
+            return Err(DataFusionError::External(Box::new(BatchSizeError)));
+        }
+
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DbfExec {
+    table_path: PathBuf,
+    memo_path: Option<PathBuf>,
+    columns: Vec<String>,
+    schema: SchemaRef,
+    batch_size: usize,
+    limit: Option<usize>,
+    properties: PlanProperties,
+}
+
+impl DbfExec {
+    // stops early when `send` returns false
+    fn read<F>(&self, mut send: F) -> Result<()>
+    where
+        F: FnMut(RecordBatch) -> bool,
+    {
+        let batch_size = self
+            .limit
+            .map_or(self.batch_size, |l| l.clamp(1, self.batch_size));
+        let mut batches = crate::read_batches(
+            self.table_path.as_path(),
+            self.memo_path.as_deref(),
+            batch_size,
+        )
+        .map_err(external_error)?;
+        batches
+            .set_projection(&self.columns)
+            .map_err(|e| DataFusionError::External(e.into()))?;
+
+        let mut remaining = self.limit.unwrap_or(usize::MAX);
+        for batch in batches {
+            if remaining == 0 {
+                break;
+            }
+
+            let batch = batch.map_err(external_error)?;
+            let mut batch = to_record_batch(&batch, self.schema.clone())?;
+            if batch.num_rows() > remaining {
+                batch = batch.slice(0, remaining);
+            }
+            remaining -= batch.num_rows();
+
+            if !send(batch) {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DisplayAs for DbfExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DbfExec: file={}", self.table_path.display())?;
+        if let Some(limit) = self.limit {
+            write!(f, ", limit={}", limit)?;
+        }
+        Ok(())
+    }
+}
+
+impl ExecutionPlan for DbfExec {
+    fn name(&self) -> &str {
+        "DbfExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.properties
+    }
+
+    fn children(&self) -> Vec<&Arc<dyn ExecutionPlan>> {
+        Vec::new()
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let exec = self.clone();
+        let mut builder = RecordBatchReceiverStreamBuilder::new(self.schema.clone(), 2);
+        let tx = builder.tx();
+        // the receiver is gone when the query was cancelled or has enough rows
+        builder.spawn_blocking(move || exec.read(|batch| tx.blocking_send(Ok(batch)).is_ok()));
+        Ok(builder.build())
+    }
+}
+
+#[async_trait]
+impl TableProvider for DbfTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let projected_schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+        let columns = projected_schema
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        let properties = PlanProperties::new(
+            EquivalenceProperties::new(projected_schema.clone()),
+            Partitioning::UnknownPartitioning(1),
+            EmissionType::Incremental,
+            Boundedness::Bounded,
+        );
+
+        Ok(Arc::new(DbfExec {
+            table_path: self.table_path.clone(),
+            memo_path: self.memo_path.clone(),
+            columns,
+            schema: projected_schema,
+            batch_size: self.batch_size,
+            limit,
+            properties,
+        }))
+    }
+}
+
+pub fn register_dbf<P: AsRef<Path>>(
+    context: &SessionContext,
+    table_name: &str,
+    table_path: P,
+    memo_path: Option<P>,
+) -> Result<()> {
+    let table = DbfTable::try_new(table_path, memo_path)?;
+    context.register_table(table_name, Arc::new(table))?;
+    Ok(())
+}
+
+pub fn register_dbf_directory<P: AsRef<Path>>(
+    context: &SessionContext,
+    directory: P,
+) -> Result<Vec<String>> {
+    let mut table_names = Vec::new();
+
+    for entry in fs::read_dir(directory)? {
+        let table_path = entry?.path();
+        let is_table = table_path.is_file()
+            && table_path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("dbf"));
+        if !is_table {
+            continue;
+        }
+
+        let table_name = match table_path.file_stem() {
+            Some(stem) => stem.to_string_lossy().to_lowercase(),
+            None => continue,
+        };
+        let memo_path = crate::find_memo_file(&table_path);
+        register_dbf(context, &table_name, table_path, memo_path)?;
+        table_names.push(table_name);
+    }
+
+    table_names.sort();
+    Ok(table_names)
+}
+
+#[derive(Debug, Default)]
+pub struct DbfUrlTableFactory;
+
+#[async_trait]
+impl UrlTableFactory for DbfUrlTableFactory {
+    async fn try_new(&self, url: &str) -> Result<Option<Arc<dyn TableProvider>>> {
+        let table_path = Path::new(url);
+        let is_table = table_path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("dbf"))
+            && table_path.is_file();
+        if !is_table {
+            return Ok(None);
+        }
+
+        let memo_path = crate::find_memo_file(table_path);
+        let table = DbfTable::try_new(table_path.to_path_buf(), memo_path)?;
+        Ok(Some(Arc::new(table)))
+    }
+}
+
+// lets queries read dbf files by path, as in SELECT * FROM 'customers.dbf'
+pub fn enable_dbf_url_table(context: SessionContext) -> SessionContext {
+    let catalog_list = Arc::new(DynamicFileCatalog::new(
+        context.state().catalog_list().clone(),
+        Arc::new(DbfUrlTableFactory),
+    ));
+    SessionStateBuilder::new_from_existing(context.state())
+        .with_catalog_list(catalog_list)
+        .build()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use datafusion::arrow::array::{Array, StringArray};
+
+    use super::*;
+    use crate::dbf::{DbfWriter, FieldType, Version};
+    use crate::value::Value;
+
+    fn write_table(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("dbf-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        let table_path = directory.join("customers.dbf");
+
+        let fields = vec![FieldInfo::with_type("NAME", FieldType::Character, 10, 0)];
+        let file = File::create(&table_path).unwrap();
+        let mut writer = DbfWriter::new(file, Version::DBase3, 0, fields).unwrap();
+        for name in ["Ana", "Bruno"] {
+            writer.write_record(&[Value::Str(name.to_owned())]).unwrap();
+        }
+        writer.finish().unwrap();
+        table_path
+    }
+
+    async fn names(context: &SessionContext, sql: &str) -> Vec<String> {
+        let batches = context.sql(sql).await.unwrap().collect().await.unwrap();
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let column = batch.column(0).as_any().downcast_ref::<StringArray>();
+                let column = column.unwrap().clone();
+                (0..column.len())
+                    .map(|i| column.value(i).to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn column_names_are_lowercased() {
+        let table_path = write_table("datafusion-lowercase");
+        let context = SessionContext::new();
+        register_dbf(&context, "customers", &table_path, None).unwrap();
+        let table = DbfTable::try_new(&table_path, None).unwrap();
+        let table = table.with_lowercase_names(false);
+        context.register_table("stored", Arc::new(table)).unwrap();
+
+        let lowercase = names(&context, "SELECT name FROM customers ORDER BY name").await;
+        let stored = names(&context, "SELECT \"NAME\" FROM stored ORDER BY \"NAME\"").await;
+        fs::remove_dir_all(table_path.parent().unwrap()).unwrap();
+
+        assert_eq!(lowercase, vec!["Ana", "Bruno"]);
+        assert_eq!(stored, vec!["Ana", "Bruno"]);
+    }
+
+    #[tokio::test]
+    async fn tables_are_queried_by_path() {
+        let table_path = write_table("datafusion-url");
+        let context = enable_dbf_url_table(SessionContext::new());
+
+        let sql = format!(
+            "SELECT name FROM '{}' WHERE name <> 'Ana'",
+            table_path.display()
+        );
+        let found = names(&context, &sql).await;
+        let missing = context.sql("SELECT name FROM 'missing.dbf'").await;
+        fs::remove_dir_all(table_path.parent().unwrap()).unwrap();
+
+        assert_eq!(found, vec!["Bruno"]);
+        assert!(missing.is_err());
+    }
+}
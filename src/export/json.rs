@@ -0,0 +1,247 @@
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use super::BinaryEncoding;
+use crate::record::{DynamicRecordIterator, Record};
+use crate::value::Value;
+
+pub const RECORD_NUMBER_KEY: &str = "_recno";
+pub const DELETED_KEY: &str = "_deleted";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JsonFormat {
+    Array,
+    Lines,
+}
+
+#[derive(Clone, Debug)]
+pub struct JsonOptions {
+    pub format: JsonFormat,
+    pub include_record_number: bool,
+    pub include_deleted: bool,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            format: JsonFormat::Array,
+            include_record_number: false,
+            include_deleted: false,
+        }
+    }
+}
+
+struct JsonValue<'a>(&'a Value);
+
+impl<'a> Serialize for JsonValue<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Bytes(val) => serializer.serialize_str(&BinaryEncoding::Base64.encode(val)),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+struct JsonRecord<'a> {
+    record: &'a Record,
+    include_record_number: bool,
+    include_deleted: bool,
+}
+
+impl<'a> Serialize for JsonRecord<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+
+        if self.include_record_number {
+            map.serialize_entry(RECORD_NUMBER_KEY, &self.record.record_number())?;
+        }
+
+        if self.include_deleted {
+            map.serialize_entry(DELETED_KEY, &self.record.is_deleted())?;
+        }
+
+        for (name, value) in self.record.iter() {
+            map.serialize_entry(name, &JsonValue(value))?;
+        }
+
+        map.end()
+    }
+}
+
+pub struct JsonWriter<W: Write> {
+    writer: W,
+    options: JsonOptions,
+    record_count: usize,
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(writer: W, options: JsonOptions) -> Self {
+        Self {
+            writer,
+            options,
+            record_count: 0,
+        }
+    }
+
+    pub fn record_count(&self) -> usize {
+        self.record_count
+    }
+
+    pub fn write_record(&mut self, record: &Record) -> Result<(), Box<dyn std::error::Error>> {
+        let json_record = JsonRecord {
+            record,
+            include_record_number: self.options.include_record_number,
+            include_deleted: self.options.include_deleted,
+        };
+
+        match self.options.format {
+            JsonFormat::Array if self.record_count > 0 => self.writer.write_all(b",\n")?,
+            JsonFormat::Array => self.writer.write_all(b"[\n")?,
+            JsonFormat::Lines => {}
+        }
+
+        serde_json::to_writer(&mut self.writer, &json_record)?;
+
+        if self.options.format == JsonFormat::Lines {
+            self.writer.write_all(b"\n")?;
+        }
+
+        self.record_count += 1;
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, std::io::Error> {
+        if self.options.format == JsonFormat::Array {
+            if self.record_count == 0 {
+                self.writer.write_all(b"[")?;
+            }
+            self.writer.write_all(b"\n]\n")?;
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+pub fn write_json_records<R, W>(
+    mut records: DynamicRecordIterator<R>,
+    writer: W,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    records.set_include_deleted(options.include_deleted);
+
+    let mut writer = JsonWriter::new(writer, options.clone());
+    for record in records {
+        writer.write_record(&record?)?;
+    }
+
+    let record_count = writer.record_count();
+    writer.finish()?;
+    Ok(record_count)
+}
+
+pub fn write_json<P, W>(
+    table_path: P,
+    memo_path: Option<P>,
+    writer: W,
+    options: &JsonOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    W: Write,
+{
+    let records = crate::read_records(table_path, memo_path)?;
+    write_json_records(records, writer, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfReader, DbfWriter, FieldInfo, FieldType, Version};
+    use crate::model::Date;
+
+    fn write(options: &JsonOptions) -> String {
+        let fields = vec![
+            FieldInfo::with_type("ZED", FieldType::Character, 5, 0),
+            FieldInfo::with_type("ALPHA", FieldType::Numeric, 6, 2),
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::VisualFoxPro, 0, fields).unwrap();
+        writer
+            .write_record(&[
+                Value::Str("a".to_owned()),
+                Value::Float(1.5),
+                Value::Date(Date::new(2024, 2, 29)),
+            ])
+            .unwrap();
+        writer
+            .write_record(&[Value::Str("b".to_owned()), Value::Null, Value::Null])
+            .unwrap();
+        let mut table = writer.finish().unwrap().into_inner();
+        // the second record is marked as deleted
+        let header_length = u16::from_le_bytes([table[8], table[9]]) as usize;
+        let record_length = u16::from_le_bytes([table[10], table[11]]) as usize;
+        table[header_length + record_length] = b'*';
+
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+        let mut output = Vec::new();
+        write_json_records(DynamicRecordIterator::new(reader), &mut output, options).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn array_keeps_the_order_of_the_table() {
+        let json = write(&JsonOptions::default());
+
+        assert_eq!(
+            json,
+            "[\n{\"ZED\":\"a\",\"ALPHA\":1.5,\"SINCE\":\"2024-02-29\"}\n]\n"
+        );
+    }
+
+    #[test]
+    fn lines_with_record_numbers_and_deleted_records() {
+        let options = JsonOptions {
+            format: JsonFormat::Lines,
+            include_record_number: true,
+            include_deleted: true,
+        };
+
+        let json = write(&options);
+
+        let lines = json.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("{\"_recno\":1,\"_deleted\":false,\"ZED\":\"a\""));
+        assert_eq!(
+            lines[1],
+            "{\"_recno\":2,\"_deleted\":true,\"ZED\":\"b\",\"ALPHA\":null,\"SINCE\":null}"
+        );
+    }
+
+    #[test]
+    fn binary_values_are_base64() {
+        let json = serde_json::to_string(&JsonValue(&Value::Bytes(vec![0xca, 0xfe]))).unwrap();
+        assert_eq!(json, "\"yv4=\"");
+    }
+
+    #[test]
+    fn empty_array() {
+        let writer = JsonWriter::new(Vec::new(), JsonOptions::default());
+        assert_eq!(writer.finish().unwrap(), b"[\n]\n");
+    }
+}
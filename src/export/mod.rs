@@ -0,0 +1,42 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "datafusion")]
+pub mod datafusion;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "polars")]
+pub mod polars;
+pub mod sql;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+#[cfg(any(feature = "csv", feature = "json"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BinaryEncoding {
+    Base64,
+    Hex,
+}
+
+#[cfg(any(feature = "csv", feature = "json"))]
+impl BinaryEncoding {
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        use base64::Engine;
+        use std::fmt::Write;
+
+        match self {
+            BinaryEncoding::Base64 => base64::engine::general_purpose::STANDARD.encode(bytes),
+            BinaryEncoding::Hex => {
+                bytes
+                    .iter()
+                    .fold(String::with_capacity(bytes.len() * 2), |mut acc, b| {
+                        let _ = write!(acc, "{:02x}", b);
+                        acc
+                    })
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+
+pub use parquet::basic::Compression;
+
+use super::arrow::ArrowBatchIterator;
+
+#[derive(Clone, Debug)]
+pub struct ParquetOptions {
+    pub batch_size: usize,
+    pub row_group_size: usize,
+    pub compression: Compression,
+}
+
+impl Default for ParquetOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 1024,
+            row_group_size: 1024 * 1024,
+            compression: Compression::SNAPPY,
+        }
+    }
+}
+
+pub fn write_parquet<R, W>(
+    batches: ArrowBatchIterator<R>,
+    writer: W,
+    options: &ParquetOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: Read + Seek,
+    W: Write + Send,
+{
+    let properties = WriterProperties::builder()
+        .set_max_row_group_size(options.row_group_size)
+        .set_compression(options.compression)
+        .build();
+
+    let mut writer = ArrowWriter::try_new(writer, batches.schema(), Some(properties))?;
+    let mut row_count = 0;
+
+    for batch in batches {
+        let batch = batch?;
+        row_count += batch.num_rows();
+        writer.write(&batch)?;
+    }
+
+    writer.close()?;
+    Ok(row_count)
+}
+
+pub fn convert_to_parquet<P, Q>(
+    table_path: P,
+    memo_path: Option<P>,
+    output_path: Q,
+    options: &ParquetOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let table_path = table_path.as_ref();
+    let memo_path = memo_path
+        .map(|p| p.as_ref().to_path_buf())
+        .or_else(|| crate::find_memo_file(table_path));
+
+    let batches = crate::read_arrow(table_path, memo_path.as_deref(), options.batch_size)?;
+    let output = File::create(output_path)?;
+    write_parquet(batches, output, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use arrow::array::{Array, StringArray};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+    use crate::dbf::{DbfWriter, FieldInfo, FieldType, Version};
+    use crate::value::Value;
+
+    #[test]
+    fn memo_file_is_found_next_to_the_table() {
+        let directory = std::env::temp_dir().join(format!("dbf-parquet-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("NOTE", FieldType::Character, 10, 0),
+        ];
+        let table = File::create(directory.join("notes.dbf")).unwrap();
+        let mut writer = DbfWriter::new(table, Version::DBase3, 0, fields).unwrap();
+        let record = |name: &str, note: &str| [Value::Str(name.into()), Value::Str(note.into())];
+        writer.write_record(&record("first", "1")).unwrap();
+        writer.write_record(&record("second", "")).unwrap();
+        let mut table = writer.finish().unwrap();
+        // memo fields cannot be written, so NOTE is turned into one pointing at the first block
+        table.seek(std::io::SeekFrom::Start(32 + 32 + 11)).unwrap();
+        table.write_all(b"M").unwrap();
+
+        let mut memo = vec![0u8; 512];
+        memo[..4].copy_from_slice(&2u32.to_le_bytes());
+        memo.extend_from_slice(b"hello memo\x1a\x1a");
+        fs::write(directory.join("notes.dbt"), memo).unwrap();
+
+        let output_path = directory.join("notes.parquet");
+        let options = ParquetOptions::default();
+        let row_count =
+            convert_to_parquet(directory.join("notes.dbf"), None, &output_path, &options).unwrap();
+
+        let output = File::open(&output_path).unwrap();
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(output)
+            .unwrap()
+            .build()
+            .unwrap();
+        let batch = reader.next().unwrap().unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(row_count, 2);
+        let notes = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(notes.value(0), "hello memo");
+        assert!(notes.is_null(1));
+    }
+}
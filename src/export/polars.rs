@@ -0,0 +1,163 @@
+use std::path::{Path, PathBuf};
+
+use polars::prelude::{
+    DataFrame, DataType, Int128Chunked, IntoSeries, NamedFrom, NewChunkedArray, PolarsResult,
+    Series, TimeUnit,
+};
+
+use crate::batch::{Batch, Column};
+use crate::dbf::{FieldInfo, FieldType};
+
+// same as the Arrow export
+const MAX_DECIMAL_PRECISION: usize = 38;
+const CURRENCY_PRECISION: usize = 19;
+const CURRENCY_SCALE: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct DataFrameOptions {
+    pub memo_path: Option<PathBuf>,
+    pub columns: Option<Vec<String>>,
+    pub n_rows: Option<usize>,
+    pub batch_size: usize,
+}
+
+impl Default for DataFrameOptions {
+    fn default() -> Self {
+        Self {
+            memo_path: None,
+            columns: None,
+            n_rows: None,
+            batch_size: 64 * 1024,
+        }
+    }
+}
+
+fn to_series(field: &FieldInfo, column: &Column) -> PolarsResult<Series> {
+    let name = field.name.as_str().into();
+    match column {
+        Column::Logical(values) => Ok(Series::new(name, values)),
+        Column::Character(values) => Ok(Series::new(name, values)),
+        Column::Integer(values) => {
+            let series = Series::new(name, values);
+            match field.field_type {
+                FieldType::Integer => series.cast(&DataType::Int32),
+                _ => Ok(series),
+            }
+        }
+        Column::Float(values) => Ok(Series::new(name, values)),
+        Column::Decimal(values) => Int128Chunked::from_iter_options(name, values.iter().copied())
+            .into_decimal(
+                Some(field.length.min(MAX_DECIMAL_PRECISION)),
+                field.decimal_count,
+            )
+            .map(IntoSeries::into_series),
+        Column::Currency(values) => {
+            let units = values.iter().map(|v| v.map(|v| v.units as i128));
+            Int128Chunked::from_iter_options(name, units)
+                .into_decimal(Some(CURRENCY_PRECISION), CURRENCY_SCALE)
+                .map(IntoSeries::into_series)
+        }
+        Column::Date(values) => {
+            let days = values
+                .iter()
+                .map(|v| v.as_ref().map(|d| d.days_since_epoch()))
+                .collect::<Vec<_>>();
+            Series::new(name, days).cast(&DataType::Date)
+        }
+        Column::Timestamp(values) => {
+            let milliseconds = values
+                .iter()
+                .map(|v| v.as_ref().map(|t| t.milliseconds_since_epoch()))
+                .collect::<Vec<_>>();
+            Series::new(name, milliseconds).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+        }
+        Column::Bytes(values) => {
+            let values = values.iter().map(|v| v.as_deref()).collect::<Vec<_>>();
+            Ok(Series::new(name, values))
+        }
+    }
+}
+
+pub fn to_dataframe(batch: &Batch) -> PolarsResult<DataFrame> {
+    let columns = batch
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, column)| to_series(field, column).map(From::from))
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    DataFrame::new(columns)
+}
+
+pub fn read_dataframe<P>(
+    table_path: P,
+    options: &DataFrameOptions,
+) -> Result<DataFrame, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let table_path = table_path.as_ref();
+    let memo_path = options
+        .memo_path
+        .clone()
+        .or_else(|| crate::find_memo_file(table_path));
+
+    let batch_size = match options.n_rows {
+        Some(n_rows) => options.batch_size.min(n_rows).max(1),
+        None => options.batch_size,
+    };
+
+    let mut batches = crate::read_batches(table_path.to_path_buf(), memo_path, batch_size)?;
+    if let Some(columns) = &options.columns {
+        batches.set_projection(columns)?;
+    }
+
+    let fields = batches.fields();
+    let mut dataframe: Option<DataFrame> = None;
+    let mut remaining = options.n_rows.unwrap_or(usize::MAX);
+
+    for batch in batches {
+        if remaining == 0 {
+            break;
+        }
+
+        let mut batch_frame = to_dataframe(&batch?)?;
+        if batch_frame.height() > remaining {
+            batch_frame = batch_frame.head(Some(remaining));
+        }
+        remaining -= batch_frame.height();
+
+        match dataframe.as_mut() {
+            Some(dataframe) => {
+                dataframe.vstack_mut(&batch_frame)?;
+            }
+            None => dataframe = Some(batch_frame),
+        }
+    }
+
+    let mut dataframe = match dataframe {
+        Some(dataframe) => dataframe,
+        None => to_dataframe(&Batch::empty(fields))?,
+    };
+
+    dataframe.align_chunks();
+    Ok(dataframe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimal_columns_keep_their_digits() {
+        let field = FieldInfo::with_type("AMOUNT", FieldType::Numeric, 20, 2);
+        let column = Column::Decimal(vec![Some(1_234_567_890_123_456_789), None]);
+
+        let series = to_series(&field, &column).unwrap();
+
+        assert_eq!(series.dtype(), &DataType::Decimal(Some(20), Some(2)));
+        let values = series.decimal().unwrap();
+        assert_eq!(values.get(0), Some(1_234_567_890_123_456_789));
+        assert_eq!(values.get(1), None);
+    }
+}
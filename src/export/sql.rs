@@ -0,0 +1,329 @@
+use std::io::{Read, Seek, Write};
+
+use crate::dbf::{FieldInfo, FieldType};
+use crate::record::DynamicRecordIterator;
+use crate::value::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SqlDialect {
+    PostgreSql,
+    MySql,
+    SqlServer,
+}
+
+impl SqlDialect {
+    pub fn quote_identifier(&self, name: &str) -> String {
+        match self {
+            SqlDialect::PostgreSql => format!("\"{}\"", name.replace('"', "\"\"")),
+            SqlDialect::MySql => format!("`{}`", name.replace('`', "``")),
+            SqlDialect::SqlServer => format!("[{}]", name.replace(']', "]]")),
+        }
+    }
+}
+
+// longer VARCHAR and VARBINARY columns must be declared as MAX
+const SQL_SERVER_MAX_LENGTH: usize = 8000;
+
+pub fn sql_column_type(field: &FieldInfo, dialect: SqlDialect) -> String {
+    match (&field.field_type, dialect) {
+        (FieldType::Logical, SqlDialect::SqlServer) => "BIT".to_owned(),
+        (FieldType::Logical, _) => "BOOLEAN".to_owned(),
+        (FieldType::Character, SqlDialect::SqlServer)
+        | (FieldType::Varchar, SqlDialect::SqlServer)
+            if field.length > SQL_SERVER_MAX_LENGTH =>
+        {
+            "VARCHAR(MAX)".to_owned()
+        }
+        (FieldType::Character, _) | (FieldType::Varchar, _) => {
+            format!("VARCHAR({})", field.length)
+        }
+        (FieldType::Integer, SqlDialect::SqlServer) => "INT".to_owned(),
+        (FieldType::Integer, _) => "INTEGER".to_owned(),
+        (FieldType::Currency, SqlDialect::SqlServer) => "MONEY".to_owned(),
+        (FieldType::Currency, _) => "NUMERIC(19, 4)".to_owned(),
+        (FieldType::Numeric, SqlDialect::SqlServer) if field.is_integral() && field.length < 10 => {
+            "INT".to_owned()
+        }
+        (FieldType::Numeric, _) if field.is_integral() && field.length < 10 => "INTEGER".to_owned(),
+        (FieldType::Numeric, _) if field.is_integral() => "BIGINT".to_owned(),
+        (FieldType::Numeric, _) => format!("NUMERIC({}, {})", field.length, field.decimal_count),
+        (FieldType::Float, SqlDialect::PostgreSql)
+        | (FieldType::Double, SqlDialect::PostgreSql) => "DOUBLE PRECISION".to_owned(),
+        (FieldType::Float, SqlDialect::MySql) | (FieldType::Double, SqlDialect::MySql) => {
+            "DOUBLE".to_owned()
+        }
+        (FieldType::Float, SqlDialect::SqlServer) | (FieldType::Double, SqlDialect::SqlServer) => {
+            "FLOAT".to_owned()
+        }
+        (FieldType::Date, _) => "DATE".to_owned(),
+        (FieldType::Timestamp, SqlDialect::PostgreSql) => "TIMESTAMP(3)".to_owned(),
+        (FieldType::Timestamp, SqlDialect::MySql) => "DATETIME(3)".to_owned(),
+        (FieldType::Timestamp, SqlDialect::SqlServer) => "DATETIME2(3)".to_owned(),
+        (FieldType::Memo, SqlDialect::PostgreSql) => "TEXT".to_owned(),
+        (FieldType::Memo, SqlDialect::MySql) => "LONGTEXT".to_owned(),
+        (FieldType::Memo, SqlDialect::SqlServer) => "NVARCHAR(MAX)".to_owned(),
+        (FieldType::Binary, SqlDialect::PostgreSql)
+        | (FieldType::General, SqlDialect::PostgreSql) => "BYTEA".to_owned(),
+        (FieldType::Binary, SqlDialect::MySql) | (FieldType::General, SqlDialect::MySql) => {
+            "LONGBLOB".to_owned()
+        }
+        (FieldType::Binary, SqlDialect::SqlServer)
+        | (FieldType::General, SqlDialect::SqlServer) => "VARBINARY(MAX)".to_owned(),
+        (FieldType::Varbinary, SqlDialect::PostgreSql) => "BYTEA".to_owned(),
+        (FieldType::Varbinary, SqlDialect::SqlServer) if field.length > SQL_SERVER_MAX_LENGTH => {
+            "VARBINARY(MAX)".to_owned()
+        }
+        (FieldType::Varbinary, _) => format!("VARBINARY({})", field.length),
+    }
+}
+
+pub fn create_table_statement(
+    table_name: &str,
+    fields: &[FieldInfo],
+    dialect: SqlDialect,
+) -> String {
+    let columns = fields
+        .iter()
+        .map(|f| {
+            format!(
+                "    {} {}",
+                dialect.quote_identifier(&f.name),
+                sql_column_type(f, dialect)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n");
+
+    format!(
+        "CREATE TABLE {} (\n{}\n);\n",
+        dialect.quote_identifier(table_name),
+        columns
+    )
+}
+
+fn escape_copy_text(value: &str, output: &mut String) {
+    for c in value.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '\t' => output.push_str("\\t"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            c => output.push(c),
+        }
+    }
+}
+
+fn write_copy_value(field: &FieldInfo, value: &Value, output: &mut String) {
+    match value {
+        Value::Str(val) => escape_copy_text(val, output),
+        Value::Int(val) => output.push_str(&val.to_string()),
+        Value::Float(val) if field.field_type == FieldType::Double => {
+            output.push_str(&val.to_string())
+        }
+        Value::Float(val) => output.push_str(&format!("{:.*}", field.decimal_count, val)),
+        Value::Currency(val) => output.push_str(&val.to_string()),
+        Value::Bool(true) => output.push('t'),
+        Value::Bool(false) => output.push('f'),
+        // blank dates stored as zeros are not valid dates for PostgreSQL
+        Value::Date(val) if !val.is_valid() => output.push_str("\\N"),
+        Value::Date(val) => output.push_str(&val.to_string()),
+        Value::Timestamp(val) if !val.date.is_valid() => output.push_str("\\N"),
+        Value::Timestamp(val) => output.push_str(&val.format("%Y-%m-%d %H:%M:%S.%f")),
+        Value::Bytes(val) => {
+            // bytea hex format, with the backslash escaped for the copy text format
+            output.push_str("\\\\x");
+            for b in val {
+                output.push_str(&format!("{:02x}", b));
+            }
+        }
+        Value::Null => output.push_str("\\N"),
+    }
+}
+
+pub fn write_pg_copy<R, W>(
+    table_name: &str,
+    records: DynamicRecordIterator<R>,
+    mut writer: W,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: Read + Seek,
+    W: Write,
+{
+    let dialect = SqlDialect::PostgreSql;
+    let fields = records.fields().to_vec();
+    let columns = fields
+        .iter()
+        .map(|f| dialect.quote_identifier(&f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    writeln!(
+        writer,
+        "COPY {} ({}) FROM STDIN;",
+        dialect.quote_identifier(table_name),
+        columns
+    )?;
+
+    let mut line = String::new();
+    let mut record_count = 0;
+
+    for record in records {
+        let record = record?;
+        line.clear();
+
+        for (i, (field, value)) in fields.iter().zip(record.values()).enumerate() {
+            if i > 0 {
+                line.push('\t');
+            }
+            write_copy_value(field, value, &mut line);
+        }
+
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+        record_count += 1;
+    }
+
+    writer.write_all(b"\\.\n")?;
+    writer.flush()?;
+    Ok(record_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfReader, DbfWriter, Version};
+    use crate::model::Date;
+
+    #[test]
+    fn column_types() {
+        let field = |field_type, length, decimal_count| {
+            FieldInfo::with_type("F", field_type, length, decimal_count)
+        };
+        let types = |field: FieldInfo| {
+            [
+                SqlDialect::PostgreSql,
+                SqlDialect::MySql,
+                SqlDialect::SqlServer,
+            ]
+            .iter()
+            .map(|dialect| sql_column_type(&field, *dialect))
+            .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            types(field(FieldType::Logical, 1, 0)),
+            ["BOOLEAN", "BOOLEAN", "BIT"]
+        );
+        assert_eq!(
+            types(field(FieldType::Numeric, 9, 0)),
+            ["INTEGER", "INTEGER", "INT"]
+        );
+        assert_eq!(
+            types(field(FieldType::Numeric, 18, 0)),
+            ["BIGINT", "BIGINT", "BIGINT"]
+        );
+        assert_eq!(
+            types(field(FieldType::Numeric, 20, 0)),
+            ["NUMERIC(20, 0)", "NUMERIC(20, 0)", "NUMERIC(20, 0)"]
+        );
+        assert_eq!(
+            types(field(FieldType::Numeric, 8, 2)),
+            ["NUMERIC(8, 2)", "NUMERIC(8, 2)", "NUMERIC(8, 2)"]
+        );
+        assert_eq!(
+            types(field(FieldType::Currency, 8, 4)),
+            ["NUMERIC(19, 4)", "NUMERIC(19, 4)", "MONEY"]
+        );
+        assert_eq!(
+            types(field(FieldType::Timestamp, 8, 0)),
+            ["TIMESTAMP(3)", "DATETIME(3)", "DATETIME2(3)"]
+        );
+        assert_eq!(
+            types(field(FieldType::Memo, 10, 0)),
+            ["TEXT", "LONGTEXT", "NVARCHAR(MAX)"]
+        );
+        assert_eq!(
+            types(field(FieldType::General, 10, 0)),
+            ["BYTEA", "LONGBLOB", "VARBINARY(MAX)"]
+        );
+    }
+
+    #[test]
+    fn create_table_quotes_identifiers() {
+        let fields = vec![FieldInfo::with_type("NAME", FieldType::Character, 10, 0)];
+
+        assert_eq!(
+            create_table_statement("my\"table", &fields, SqlDialect::PostgreSql),
+            "CREATE TABLE \"my\"\"table\" (\n    \"NAME\" VARCHAR(10)\n);\n"
+        );
+        assert_eq!(SqlDialect::MySql.quote_identifier("a`b"), "`a``b`");
+        assert_eq!(SqlDialect::SqlServer.quote_identifier("a]b"), "[a]]b]");
+    }
+
+    #[test]
+    fn pg_copy_escaping() {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("PRICE", FieldType::Numeric, 8, 2),
+            FieldInfo::with_type("ACTIVE", FieldType::Logical, 1, 0),
+            FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        writer
+            .write_record(&[
+                Value::Str("a\\b\tc\nd".to_owned()),
+                Value::Float(1.5),
+                Value::Bool(true),
+                Value::Date(Date::new(2024, 2, 29)),
+            ])
+            .unwrap();
+        writer
+            .write_record(&[
+                Value::Str(String::new()),
+                Value::Null,
+                Value::Bool(false),
+                Value::Null,
+            ])
+            .unwrap();
+        let mut table = writer.finish().unwrap().into_inner();
+        // blank dates are sometimes stored as zeros
+        let header_length = u16::from_le_bytes([table[8], table[9]]) as usize;
+        let record_length = u16::from_le_bytes([table[10], table[11]]) as usize;
+        let date = header_length + record_length + record_length - 8;
+        table[date..date + 8].copy_from_slice(b"00000000");
+
+        let reader = DbfReader::from_reader(Cursor::new(table), None).unwrap();
+        let mut output = Vec::new();
+        let record_count =
+            write_pg_copy("items", DynamicRecordIterator::new(reader), &mut output).unwrap();
+
+        assert_eq!(record_count, 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "COPY \"items\" (\"NAME\", \"PRICE\", \"ACTIVE\", \"SINCE\") FROM STDIN;\n\
+             a\\\\b\\tc\\nd\t1.50\tt\t2024-02-29\n\
+             \\N\t\\N\tf\t\\N\n\
+             \\.\n"
+        );
+    }
+
+    #[test]
+    fn sql_server_long_columns() {
+        let column = |field_type, length| {
+            let field = FieldInfo::with_type("F", field_type, length, 0);
+            sql_column_type(&field, SqlDialect::SqlServer)
+        };
+
+        assert_eq!(column(FieldType::Character, 8000), "VARCHAR(8000)");
+        assert_eq!(column(FieldType::Character, 8001), "VARCHAR(MAX)");
+        assert_eq!(column(FieldType::Varbinary, 8001), "VARBINARY(MAX)");
+        let field = FieldInfo::with_type("F", FieldType::Character, 8001, 0);
+        assert_eq!(
+            sql_column_type(&field, SqlDialect::PostgreSql),
+            "VARCHAR(8001)"
+        );
+    }
+}
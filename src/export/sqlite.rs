@@ -0,0 +1,183 @@
+use std::fs;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+use rusqlite::types::{ToSql, ToSqlOutput, ValueRef};
+use rusqlite::{params_from_iter, Connection};
+
+use crate::dbf::{FieldInfo, FieldType};
+use crate::record::DynamicRecordIterator;
+use crate::value::Value;
+
+#[derive(Clone, Debug)]
+pub struct SqliteOptions {
+    pub transaction_size: usize,
+    pub replace_existing: bool,
+}
+
+impl Default for SqliteOptions {
+    fn default() -> Self {
+        Self {
+            transaction_size: 10_000,
+            replace_existing: false,
+        }
+    }
+}
+
+impl ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        let output = match self {
+            Value::Str(val) => ToSqlOutput::Borrowed(ValueRef::Text(val.as_bytes())),
+            Value::Int(val) => ToSqlOutput::from(*val),
+            Value::Float(val) => ToSqlOutput::from(*val),
+            Value::Currency(val) => ToSqlOutput::from(val.to_f64()),
+            Value::Bool(val) => ToSqlOutput::from(*val),
+            Value::Date(val) => ToSqlOutput::from(val.to_string()),
+            Value::Timestamp(val) => ToSqlOutput::from(val.format("%Y-%m-%d %H:%M:%S.%f")),
+            Value::Bytes(val) => ToSqlOutput::Borrowed(ValueRef::Blob(val)),
+            Value::Null => ToSqlOutput::Owned(rusqlite::types::Value::Null),
+        };
+
+        Ok(output)
+    }
+}
+
+pub fn sqlite_column_type(field: &FieldInfo) -> &'static str {
+    match field.field_type {
+        FieldType::Numeric if field.is_integral() => "INTEGER",
+        FieldType::Logical | FieldType::Integer => "INTEGER",
+        FieldType::Character | FieldType::Varchar | FieldType::Memo => "TEXT",
+        FieldType::Numeric | FieldType::Currency => "NUMERIC",
+        FieldType::Float | FieldType::Double => "REAL",
+        FieldType::Date | FieldType::Timestamp => "TEXT",
+        FieldType::Binary | FieldType::General | FieldType::Varbinary => "BLOB",
+    }
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub fn create_table_sql(table_name: &str, fields: &[FieldInfo]) -> String {
+    let columns = fields
+        .iter()
+        .map(|f| format!("{} {}", quote_identifier(&f.name), sqlite_column_type(f)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "CREATE TABLE {} ({})",
+        quote_identifier(table_name),
+        columns
+    )
+}
+
+fn insert_sql(table_name: &str, fields: &[FieldInfo]) -> String {
+    let columns = fields
+        .iter()
+        .map(|f| quote_identifier(&f.name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let placeholders = vec!["?"; fields.len()].join(", ");
+
+    format!(
+        "INSERT INTO {} ({}) VALUES ({})",
+        quote_identifier(table_name),
+        columns,
+        placeholders
+    )
+}
+
+pub fn import_records<R>(
+    connection: &mut Connection,
+    table_name: &str,
+    mut records: DynamicRecordIterator<R>,
+    options: &SqliteOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: Read + Seek,
+{
+    let fields = records.fields().to_vec();
+
+    if options.replace_existing {
+        connection.execute_batch(&format!(
+            "DROP TABLE IF EXISTS {}",
+            quote_identifier(table_name)
+        ))?;
+    }
+    connection.execute_batch(&create_table_sql(table_name, &fields))?;
+
+    let insert = insert_sql(table_name, &fields);
+    let mut record_count = 0;
+
+    loop {
+        let transaction = connection.transaction()?;
+        let mut inserted = 0;
+        {
+            let mut statement = transaction.prepare_cached(&insert)?;
+            for record in records.by_ref().take(options.transaction_size.max(1)) {
+                let record = record?;
+                statement.execute(params_from_iter(record.values()))?;
+                inserted += 1;
+            }
+        }
+        transaction.commit()?;
+
+        record_count += inserted;
+        if inserted < options.transaction_size.max(1) {
+            break;
+        }
+    }
+
+    Ok(record_count)
+}
+
+pub fn import_sqlite<P>(
+    connection: &mut Connection,
+    table_name: &str,
+    table_path: P,
+    memo_path: Option<P>,
+    options: &SqliteOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let records = crate::read_records(table_path, memo_path)?;
+    import_records(connection, table_name, records, options)
+}
+
+pub fn import_sqlite_directory<P, Q>(
+    directory: P,
+    database_path: Q,
+    options: &SqliteOptions,
+) -> Result<Vec<(String, usize)>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let mut table_paths = fs::read_dir(directory)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    table_paths.retain(|p| {
+        p.is_file()
+            && p.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("dbf"))
+    });
+    table_paths.sort();
+
+    let mut connection = Connection::open(database_path)?;
+    let mut imported = Vec::with_capacity(table_paths.len());
+
+    for table_path in table_paths {
+        let table_name = match table_path.file_stem() {
+            Some(stem) => stem.to_string_lossy().into_owned(),
+            None => continue,
+        };
+        let memo_path = crate::find_memo_file(&table_path);
+        let records = crate::read_records(table_path, memo_path)?;
+        let record_count = import_records(&mut connection, &table_name, records, options)?;
+        imported.push((table_name, record_count));
+    }
+
+    Ok(imported)
+}
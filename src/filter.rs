@@ -0,0 +1,766 @@
+use std::cmp::Ordering;
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::error::ExpressionError;
+use crate::model::Date;
+use crate::record::Record;
+use crate::value::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Date(Date),
+    Ident(String),
+    Logical(bool),
+    Null,
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn read_while<F: Fn(char) -> bool>(chars: &mut Peekable<Chars>, predicate: F) -> String {
+    let mut text = String::new();
+    while let Some(c) = chars.peek().copied().filter(|c| predicate(*c)) {
+        text.push(c);
+        chars.next();
+    }
+    text
+}
+
+fn parse_date_literal(text: &str) -> Result<Date, ExpressionError> {
+    let text = text.trim().trim_start_matches('^');
+    let parts = text.split(['-', '/']).collect::<Vec<_>>();
+    let invalid = || ExpressionError::new(format!("invalid date literal '{{{}}}'", text));
+
+    if parts.len() != 3 {
+        return Err(invalid());
+    }
+
+    let year = parts[0].parse().map_err(|_| invalid())?;
+    let month = parts[1].parse().map_err(|_| invalid())?;
+    let day = parts[2].parse().map_err(|_| invalid())?;
+    let date = Date::new(year, month, day);
+    if !date.is_valid() {
+        return Err(invalid());
+    }
+
+    Ok(date)
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, ExpressionError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.peek().copied() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let token =
+            match c {
+                '0'..='9' => {
+                    let mut number = read_while(&mut chars, |c| c.is_ascii_digit());
+                    // a dot followed by a letter starts an operator, as in 100.AND.
+                    if chars.peek() == Some(&'.')
+                        && !chars
+                            .clone()
+                            .nth(1)
+                            .is_some_and(|c| c.is_ascii_alphabetic())
+                    {
+                        chars.next();
+                        number.push('.');
+                        number.push_str(&read_while(&mut chars, |c| c.is_ascii_digit()));
+                    }
+                    Token::Number(number.parse().map_err(|_| {
+                        ExpressionError::new(format!("invalid number '{}'", number))
+                    })?)
+                }
+                '.' => {
+                    chars.next();
+                    if chars.peek().is_some_and(|c| c.is_ascii_digit()) {
+                        let number = read_while(&mut chars, |c| c.is_ascii_digit());
+                        Token::Number(format!("0.{}", number).parse().unwrap_or_default())
+                    } else {
+                        let word = read_while(&mut chars, |c| c.is_ascii_alphabetic());
+                        if chars.next() != Some('.') {
+                            return Err(ExpressionError::new(format!("unterminated '.{}'", word)));
+                        }
+                        match word.to_ascii_uppercase().as_str() {
+                            "AND" => Token::And,
+                            "OR" => Token::Or,
+                            "NOT" => Token::Not,
+                            "T" | "Y" => Token::Logical(true),
+                            "F" | "N" => Token::Logical(false),
+                            "NULL" => Token::Null,
+                            _ => return Err(ExpressionError::new(format!("unknown '.{}.'", word))),
+                        }
+                    }
+                }
+                '\'' | '"' | '[' => {
+                    chars.next();
+                    let end = if c == '[' { ']' } else { c };
+                    let text = read_while(&mut chars, |c| c != end);
+                    if chars.next() != Some(end) {
+                        return Err(ExpressionError::new("unterminated string"));
+                    }
+                    Token::Str(text)
+                }
+                '{' => {
+                    chars.next();
+                    let text = read_while(&mut chars, |c| c != '}');
+                    if chars.next() != Some('}') {
+                        return Err(ExpressionError::new("unterminated date literal"));
+                    }
+                    Token::Date(parse_date_literal(&text)?)
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let word = read_while(&mut chars, |c| c.is_alphanumeric() || c == '_');
+                    match word.to_ascii_uppercase().as_str() {
+                        "AND" => Token::And,
+                        "OR" => Token::Or,
+                        "NOT" => Token::Not,
+                        "NULL" => Token::Null,
+                        _ => Token::Ident(word),
+                    }
+                }
+                _ => {
+                    chars.next();
+                    let next = chars.peek().copied();
+                    let mut take = |token| {
+                        chars.next();
+                        token
+                    };
+                    match (c, next) {
+                        ('=', Some('=')) => take(Token::Eq),
+                        ('=', _) => Token::Eq,
+                        ('<', Some('>')) | ('!', Some('=')) => take(Token::Ne),
+                        ('#', _) => Token::Ne,
+                        ('<', Some('=')) => take(Token::Le),
+                        ('<', _) => Token::Lt,
+                        ('>', Some('=')) => take(Token::Ge),
+                        ('>', _) => Token::Gt,
+                        ('!', _) => Token::Not,
+                        ('$', _) => Token::Contains,
+                        ('+', _) => Token::Plus,
+                        ('-', _) => Token::Minus,
+                        ('*', _) => Token::Star,
+                        ('/', _) => Token::Slash,
+                        ('(', _) => Token::LParen,
+                        (')', _) => Token::RParen,
+                        (',', _) => Token::Comma,
+                        _ => return Err(ExpressionError::new(format!("unexpected '{}'", c))),
+                    }
+                }
+            };
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BinaryOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Literal(Value),
+    Field(String),
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Call(String, Vec<Expr>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ExpressionError> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(ExpressionError::new(format!(
+                "expected {:?}, found {:?}",
+                expected, token
+            ))),
+            None => Err(ExpressionError::new(format!(
+                "expected {:?} at the end",
+                expected
+            ))),
+        }
+    }
+
+    fn binary<F>(&mut self, operand: F, ops: &[(Token, BinaryOp)]) -> Result<Expr, ExpressionError>
+    where
+        F: Fn(&mut Self) -> Result<Expr, ExpressionError>,
+    {
+        let mut left = operand(self)?;
+        while let Some(op) = self
+            .peek()
+            .and_then(|t| ops.iter().find(|(token, _)| token == t))
+            .map(|(_, op)| *op)
+        {
+            self.position += 1;
+            let right = operand(self)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn or(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::and, &[(Token::Or, BinaryOp::Or)])
+    }
+
+    fn and(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(Self::not, &[(Token::And, BinaryOp::And)])
+    }
+
+    fn not(&mut self) -> Result<Expr, ExpressionError> {
+        if self.peek() == Some(&Token::Not) {
+            self.position += 1;
+            return Ok(Expr::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(
+            Self::additive,
+            &[
+                (Token::Eq, BinaryOp::Eq),
+                (Token::Ne, BinaryOp::Ne),
+                (Token::Lt, BinaryOp::Lt),
+                (Token::Le, BinaryOp::Le),
+                (Token::Gt, BinaryOp::Gt),
+                (Token::Ge, BinaryOp::Ge),
+                (Token::Contains, BinaryOp::Contains),
+            ],
+        )
+    }
+
+    fn additive(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(
+            Self::multiplicative,
+            &[(Token::Plus, BinaryOp::Add), (Token::Minus, BinaryOp::Sub)],
+        )
+    }
+
+    fn multiplicative(&mut self) -> Result<Expr, ExpressionError> {
+        self.binary(
+            Self::unary,
+            &[(Token::Star, BinaryOp::Mul), (Token::Slash, BinaryOp::Div)],
+        )
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExpressionError> {
+        if self.peek() == Some(&Token::Minus) {
+            self.position += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, ExpressionError> {
+        match self.next() {
+            Some(Token::Number(val)) => Ok(Expr::Literal(Value::Float(val))),
+            Some(Token::Str(val)) => Ok(Expr::Literal(Value::Str(val))),
+            Some(Token::Date(val)) => Ok(Expr::Literal(Value::Date(val))),
+            Some(Token::Logical(val)) => Ok(Expr::Literal(Value::Bool(val))),
+            Some(Token::Null) => Ok(Expr::Literal(Value::Null)),
+            Some(Token::LParen) => {
+                let expr = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                self.position += 1;
+                let mut args = Vec::new();
+                if self.peek() != Some(&Token::RParen) {
+                    args.push(self.or()?);
+                    while self.peek() == Some(&Token::Comma) {
+                        self.position += 1;
+                        args.push(self.or()?);
+                    }
+                }
+                self.expect(Token::RParen)?;
+                Ok(Expr::Call(name.to_ascii_uppercase(), args))
+            }
+            Some(Token::Ident(name)) => Ok(Expr::Field(name)),
+            Some(token) => Err(ExpressionError::new(format!("unexpected {:?}", token))),
+            None => Err(ExpressionError::new("unexpected end of expression")),
+        }
+    }
+}
+
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int(val) => Some(*val as f64),
+        Value::Float(val) => Some(*val),
+        Value::Currency(val) => Some(val.to_f64()),
+        _ => None,
+    }
+}
+
+fn compare(left: &Value, right: &Value) -> Option<Ordering> {
+    match (left, right) {
+        (Value::Str(l), Value::Str(r)) => Some(l.cmp(r)),
+        (Value::Bool(l), Value::Bool(r)) => Some(l.cmp(r)),
+        (Value::Currency(l), Value::Currency(r)) => Some(l.cmp(r)),
+        (Value::Date(l), Value::Date(r)) => Some(l.days_since_epoch().cmp(&r.days_since_epoch())),
+        (Value::Timestamp(l), Value::Timestamp(r)) => Some(
+            l.milliseconds_since_epoch()
+                .cmp(&r.milliseconds_since_epoch()),
+        ),
+        (Value::Timestamp(l), Value::Date(r)) => {
+            Some(l.date.days_since_epoch().cmp(&r.days_since_epoch()))
+        }
+        (Value::Date(_), Value::Timestamp(_)) => compare(right, left).map(Ordering::reverse),
+        (Value::Bytes(l), Value::Bytes(r)) => Some(l.cmp(r)),
+        _ => as_number(left)?.partial_cmp(&as_number(right)?),
+    }
+}
+
+fn type_error(op: BinaryOp, left: &Value, right: &Value) -> ExpressionError {
+    ExpressionError::new(format!(
+        "cannot apply {:?} to {:?} and {:?}",
+        op, left, right
+    ))
+}
+
+fn evaluate_binary(op: BinaryOp, left: Value, right: Value) -> Result<Value, ExpressionError> {
+    if left == Value::Null || right == Value::Null {
+        return Ok(Value::Null);
+    }
+
+    let ordering = |predicate: fn(Ordering) -> bool| match compare(&left, &right) {
+        Some(ordering) => Ok(Value::Bool(predicate(ordering))),
+        None => Err(type_error(op, &left, &right)),
+    };
+
+    match op {
+        BinaryOp::Eq => ordering(|o| o == Ordering::Equal),
+        BinaryOp::Ne => ordering(|o| o != Ordering::Equal),
+        BinaryOp::Lt => ordering(|o| o == Ordering::Less),
+        BinaryOp::Le => ordering(|o| o != Ordering::Greater),
+        BinaryOp::Gt => ordering(|o| o == Ordering::Greater),
+        BinaryOp::Ge => ordering(|o| o != Ordering::Less),
+        BinaryOp::Contains => match (&left, &right) {
+            (Value::Str(l), Value::Str(r)) => Ok(Value::Bool(r.contains(l.as_str()))),
+            _ => Err(type_error(op, &left, &right)),
+        },
+        BinaryOp::Add => match (&left, &right) {
+            (Value::Str(l), Value::Str(r)) => Ok(Value::Str(format!("{}{}", l, r))),
+            (Value::Date(d), n) | (n, Value::Date(d)) if as_number(n).is_some() => {
+                let days = as_number(n).unwrap_or_default() as i32;
+                Ok(Value::Date(Date::from_days_since_epoch(
+                    d.days_since_epoch() + days,
+                )))
+            }
+            _ => arithmetic(op, &left, &right, |l, r| l + r),
+        },
+        BinaryOp::Sub => match (&left, &right) {
+            (Value::Date(l), Value::Date(r)) => Ok(Value::Float(
+                (l.days_since_epoch() - r.days_since_epoch()) as f64,
+            )),
+            (Value::Date(d), n) if as_number(n).is_some() => {
+                let days = as_number(n).unwrap_or_default() as i32;
+                Ok(Value::Date(Date::from_days_since_epoch(
+                    d.days_since_epoch() - days,
+                )))
+            }
+            _ => arithmetic(op, &left, &right, |l, r| l - r),
+        },
+        BinaryOp::Mul => arithmetic(op, &left, &right, |l, r| l * r),
+        BinaryOp::Div => arithmetic(op, &left, &right, |l, r| l / r),
+        BinaryOp::And | BinaryOp::Or => unreachable!("logical operators are evaluated lazily"),
+    }
+}
+
+fn arithmetic<F>(op: BinaryOp, left: &Value, right: &Value, f: F) -> Result<Value, ExpressionError>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    match (as_number(left), as_number(right)) {
+        (Some(l), Some(r)) => Ok(Value::Float(f(l, r))),
+        _ => Err(type_error(op, left, right)),
+    }
+}
+
+fn as_logical(value: &Value) -> Result<Option<bool>, ExpressionError> {
+    match value {
+        Value::Bool(val) => Ok(Some(*val)),
+        Value::Null => Ok(None),
+        value => Err(ExpressionError::new(format!(
+            "{:?} is not a logical value",
+            value
+        ))),
+    }
+}
+
+fn call(name: &str, args: Vec<Value>) -> Result<Value, ExpressionError> {
+    let arg = match args.as_slice() {
+        [arg] => arg,
+        _ => {
+            return Err(ExpressionError::new(format!(
+                "{}() takes exactly one argument",
+                name
+            )))
+        }
+    };
+
+    let value = match (name, arg) {
+        ("EMPTY", Value::Null) => Value::Bool(true),
+        ("EMPTY", Value::Str(val)) => Value::Bool(val.trim().is_empty()),
+        ("EMPTY", Value::Int(val)) => Value::Bool(*val == 0),
+        ("EMPTY", Value::Float(val)) => Value::Bool(*val == 0.0),
+        ("EMPTY", Value::Currency(val)) => Value::Bool(val.units == 0),
+        ("EMPTY", Value::Bool(val)) => Value::Bool(!val),
+        ("EMPTY", Value::Bytes(val)) => Value::Bool(val.is_empty()),
+        ("EMPTY", _) => Value::Bool(false),
+        ("ISNULL", arg) => Value::Bool(*arg == Value::Null),
+        (_, Value::Null) => Value::Null,
+        ("UPPER", Value::Str(val)) => Value::Str(val.to_uppercase()),
+        ("LOWER", Value::Str(val)) => Value::Str(val.to_lowercase()),
+        ("TRIM", Value::Str(val)) | ("RTRIM", Value::Str(val)) => {
+            Value::Str(val.trim_end().to_owned())
+        }
+        ("LTRIM", Value::Str(val)) => Value::Str(val.trim_start().to_owned()),
+        ("ALLTRIM", Value::Str(val)) => Value::Str(val.trim().to_owned()),
+        ("LEN", Value::Str(val)) => Value::Int(val.chars().count() as i64),
+        ("ABS", arg) if as_number(arg).is_some() => {
+            Value::Float(as_number(arg).unwrap_or_default().abs())
+        }
+        ("INT", arg) if as_number(arg).is_some() => {
+            Value::Float(as_number(arg).unwrap_or_default().trunc())
+        }
+        ("YEAR", Value::Date(val)) => Value::Int(val.year as i64),
+        ("MONTH", Value::Date(val)) => Value::Int(val.month as i64),
+        ("DAY", Value::Date(val)) => Value::Int(val.day as i64),
+        ("YEAR", Value::Timestamp(val)) => Value::Int(val.date.year as i64),
+        ("MONTH", Value::Timestamp(val)) => Value::Int(val.date.month as i64),
+        ("DAY", Value::Timestamp(val)) => Value::Int(val.date.day as i64),
+        ("CTOD", Value::Str(val)) => Value::Date(parse_date_literal(val)?),
+        _ => {
+            return Err(ExpressionError::new(format!(
+                "unknown function {}() for {:?}",
+                name, arg
+            )))
+        }
+    };
+
+    Ok(value)
+}
+
+impl Expr {
+    fn evaluate(&self, record: &Record) -> Result<Value, ExpressionError> {
+        match self {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Field(name) => record
+                .get_by_name(name)
+                .cloned()
+                .ok_or_else(|| ExpressionError::new(format!("field '{}' does not exist", name))),
+            Expr::Not(expr) => {
+                let value = expr.evaluate(record)?;
+                Ok(as_logical(&value)?.map_or(Value::Null, |val| Value::Bool(!val)))
+            }
+            Expr::Neg(expr) => match expr.evaluate(record)? {
+                Value::Int(val) => Ok(Value::Int(-val)),
+                Value::Float(val) => Ok(Value::Float(-val)),
+                Value::Null => Ok(Value::Null),
+                value => Err(ExpressionError::new(format!("cannot negate {:?}", value))),
+            },
+            Expr::Binary(BinaryOp::And, left, right) => {
+                match as_logical(&left.evaluate(record)?)? {
+                    Some(false) => Ok(Value::Bool(false)),
+                    left => match (left, as_logical(&right.evaluate(record)?)?) {
+                        (_, Some(false)) => Ok(Value::Bool(false)),
+                        (Some(true), Some(true)) => Ok(Value::Bool(true)),
+                        _ => Ok(Value::Null),
+                    },
+                }
+            }
+            Expr::Binary(BinaryOp::Or, left, right) => match as_logical(&left.evaluate(record)?)? {
+                Some(true) => Ok(Value::Bool(true)),
+                left => match (left, as_logical(&right.evaluate(record)?)?) {
+                    (_, Some(true)) => Ok(Value::Bool(true)),
+                    (Some(false), Some(false)) => Ok(Value::Bool(false)),
+                    _ => Ok(Value::Null),
+                },
+            },
+            Expr::Binary(op, left, right) => {
+                evaluate_binary(*op, left.evaluate(record)?, right.evaluate(record)?)
+            }
+            Expr::Call(name, args) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.evaluate(record))
+                    .collect::<Result<_, _>>()?;
+                call(name, args)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Filter {
+    expr: Expr,
+}
+
+impl Filter {
+    pub fn parse(text: &str) -> Result<Self, ExpressionError> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            position: 0,
+        };
+
+        let expr = parser.or()?;
+        if let Some(token) = parser.peek() {
+            return Err(ExpressionError::new(format!("unexpected {:?}", token)));
+        }
+
+        Ok(Self { expr })
+    }
+
+    pub fn evaluate(&self, record: &Record) -> Result<Value, ExpressionError> {
+        self.expr.evaluate(record)
+    }
+
+    pub fn matches(&self, record: &Record) -> Result<bool, ExpressionError> {
+        self.evaluate(record)
+            .and_then(|value| as_logical(&value))
+            .map(|value| value.unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::dbf::{FieldInfo, FieldType};
+    use crate::model::Timestamp;
+
+    fn record() -> Record {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("STATE", FieldType::Character, 2, 0),
+            FieldInfo::with_type("TOTAL", FieldType::Numeric, 8, 2),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 3, 0),
+            FieldInfo::with_type("BORN", FieldType::Date, 8, 0),
+            FieldInfo::with_type("UPDATED", FieldType::Timestamp, 8, 0),
+            FieldInfo::with_type("NOTE", FieldType::Character, 10, 0),
+        ];
+        let values = vec![
+            Value::Str("  Alice ".to_owned()),
+            Value::Str("SP".to_owned()),
+            Value::Float(150.5),
+            Value::Int(3),
+            Value::Date(Date::new(1990, 5, 17)),
+            Value::Timestamp(Timestamp::new(2024, 2, 29, 13, 45, 30, 0)),
+            Value::Null,
+        ];
+        Record::new(Arc::from(fields), values, 1, false)
+    }
+
+    fn evaluate(text: &str) -> Value {
+        Filter::parse(text).unwrap().evaluate(&record()).unwrap()
+    }
+
+    fn error(text: &str) -> String {
+        match Filter::parse(text) {
+            Ok(filter) => filter.evaluate(&record()).unwrap_err().to_string(),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn operators_without_spaces() {
+        assert_eq!(evaluate("TOTAL>100.AND.STATE='SP'"), Value::Bool(true));
+        assert_eq!(evaluate("QTY=3.OR..F."), Value::Bool(true));
+        assert_eq!(evaluate("1.5*2"), Value::Float(3.0));
+        assert_eq!(evaluate(".5+1."), Value::Float(1.5));
+    }
+
+    #[test]
+    fn date_literals_are_range_checked() {
+        assert_eq!(
+            evaluate("{^2024-02-29}"),
+            Value::Date(Date::new(2024, 2, 29))
+        );
+        assert_eq!(
+            error("{^2024-02-31}"),
+            "Invalid expression: invalid date literal '{2024-02-31}'"
+        );
+        assert!(Filter::parse("{^2023-02-29}").is_err());
+        assert!(Filter::parse("{^2024-13-01}").is_err());
+        assert!(Filter::parse("{^2024-04-00}").is_err());
+        assert_eq!(
+            error("CTOD('2024-04-31')"),
+            "Invalid expression: invalid date literal '{2024-04-31}'"
+        );
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(evaluate("1 + 2 * 3"), Value::Float(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3"), Value::Float(9.0));
+        assert_eq!(evaluate("10 - 4 - 3"), Value::Float(3.0));
+        assert_eq!(evaluate("-2 * 3 + 1"), Value::Float(-5.0));
+        assert_eq!(evaluate(".T. .OR. .F. .AND. .F."), Value::Bool(true));
+        assert_eq!(evaluate("(.T. .OR. .F.) .AND. .F."), Value::Bool(false));
+        assert_eq!(evaluate(".NOT. .F. .AND. .F."), Value::Bool(false));
+        assert_eq!(evaluate("NOT QTY > 5 AND STATE = 'SP'"), Value::Bool(true));
+        assert_eq!(evaluate("! QTY + 1 = 4"), Value::Bool(false));
+        assert_eq!(evaluate("TOTAL / 2 >= QTY * 25"), Value::Bool(true));
+    }
+
+    #[test]
+    fn comparisons() {
+        assert_eq!(evaluate("QTY == 3"), Value::Bool(true));
+        assert_eq!(evaluate("QTY <> 3"), Value::Bool(false));
+        assert_eq!(evaluate("QTY # 4"), Value::Bool(true));
+        assert_eq!(evaluate("QTY != 4"), Value::Bool(true));
+        assert_eq!(evaluate("QTY < 3"), Value::Bool(false));
+        assert_eq!(evaluate("QTY <= 3"), Value::Bool(true));
+        assert_eq!(evaluate("BORN < {^2000-01-01}"), Value::Bool(true));
+        assert_eq!(evaluate("UPDATED > {^2024-02-28}"), Value::Bool(true));
+        assert_eq!(evaluate("BORN + 15 = {^1990-06-01}"), Value::Bool(true));
+        assert_eq!(
+            error("STATE > 1"),
+            "Invalid expression: cannot apply Gt to Str(\"SP\") and Float(1.0)"
+        );
+    }
+
+    #[test]
+    fn contains() {
+        assert_eq!(evaluate("'li' $ NAME"), Value::Bool(true));
+        assert_eq!(evaluate("'LI' $ NAME"), Value::Bool(false));
+        assert_eq!(evaluate("STATE $ 'RJ,SP'"), Value::Bool(true));
+        assert_eq!(evaluate("'SP' $ NOTE"), Value::Null);
+        assert!(error("1 $ NAME").starts_with("Invalid expression: cannot apply Contains"));
+    }
+
+    #[test]
+    fn null_handling() {
+        assert_eq!(evaluate(".NULL. = 1"), Value::Null);
+        assert_eq!(evaluate("NOTE = 'x'"), Value::Null);
+        assert_eq!(evaluate("NOTE + 'x'"), Value::Null);
+        assert_eq!(evaluate(".NOT. .NULL."), Value::Null);
+        assert_eq!(evaluate(".NULL. .AND. .F."), Value::Bool(false));
+        assert_eq!(evaluate(".NULL. .AND. .T."), Value::Null);
+        assert_eq!(evaluate(".NULL. .OR. .T."), Value::Bool(true));
+        assert_eq!(evaluate("NULL .OR. .F."), Value::Null);
+        assert_eq!(evaluate("UPPER(NOTE)"), Value::Null);
+
+        let filter = Filter::parse("NOTE = 'x'").unwrap();
+        assert!(!filter.matches(&record()).unwrap());
+        let filter = Filter::parse(".NOT. NOTE = 'x'").unwrap();
+        assert!(!filter.matches(&record()).unwrap());
+    }
+
+    #[test]
+    fn functions() {
+        let text = |value: &str| Value::Str(value.to_owned());
+
+        assert_eq!(evaluate("UPPER(NAME)"), text("  ALICE "));
+        assert_eq!(evaluate("LOWER('AbC')"), text("abc"));
+        assert_eq!(evaluate("TRIM(NAME)"), text("  Alice"));
+        assert_eq!(evaluate("RTRIM(NAME)"), text("  Alice"));
+        assert_eq!(evaluate("LTRIM(NAME)"), text("Alice "));
+        assert_eq!(evaluate("ALLTRIM(NAME)"), text("Alice"));
+        assert_eq!(evaluate("LEN('héllo')"), Value::Int(5));
+        assert_eq!(evaluate("EMPTY('   ')"), Value::Bool(true));
+        assert_eq!(evaluate("EMPTY(NAME)"), Value::Bool(false));
+        assert_eq!(evaluate("EMPTY(0)"), Value::Bool(true));
+        assert_eq!(evaluate("EMPTY(QTY)"), Value::Bool(false));
+        assert_eq!(evaluate("EMPTY(.F.)"), Value::Bool(true));
+        assert_eq!(evaluate("EMPTY(NOTE)"), Value::Bool(true));
+        assert_eq!(evaluate("EMPTY(BORN)"), Value::Bool(false));
+        assert_eq!(evaluate("ISNULL(NOTE)"), Value::Bool(true));
+        assert_eq!(evaluate("ISNULL(QTY)"), Value::Bool(false));
+        assert_eq!(evaluate("ABS(-2.5)"), Value::Float(2.5));
+        assert_eq!(evaluate("INT(-2.7)"), Value::Float(-2.0));
+        assert_eq!(evaluate("INT(TOTAL)"), Value::Float(150.0));
+        assert_eq!(evaluate("YEAR(BORN)"), Value::Int(1990));
+        assert_eq!(evaluate("MONTH(BORN)"), Value::Int(5));
+        assert_eq!(evaluate("DAY(BORN)"), Value::Int(17));
+        assert_eq!(evaluate("YEAR(UPDATED)"), Value::Int(2024));
+        assert_eq!(evaluate("MONTH(UPDATED)"), Value::Int(2));
+        assert_eq!(evaluate("DAY(UPDATED)"), Value::Int(29));
+        assert_eq!(
+            evaluate("CTOD('2024/02/29')"),
+            Value::Date(Date::new(2024, 2, 29))
+        );
+        assert_eq!(evaluate("upper('a')"), text("A"));
+
+        assert_eq!(
+            error("LEN(QTY)"),
+            "Invalid expression: unknown function LEN() for Int(3)"
+        );
+        assert_eq!(
+            error("UPPER('a', 'b')"),
+            "Invalid expression: UPPER() takes exactly one argument"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            error("QTY = "),
+            "Invalid expression: unexpected end of expression"
+        );
+        assert_eq!(
+            error("(QTY = 3"),
+            "Invalid expression: expected RParen at the end"
+        );
+        assert_eq!(error("'open"), "Invalid expression: unterminated string");
+        assert_eq!(error(".XOR."), "Invalid expression: unknown '.XOR.'");
+        assert_eq!(
+            error("MISSING = 1"),
+            "Invalid expression: field 'MISSING' does not exist"
+        );
+    }
+}
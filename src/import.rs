@@ -0,0 +1,657 @@
+use std::collections::HashSet;
+use std::io::{Seek, Write};
+
+use crate::dbf::{DbfWriter, Encoding, FieldInfo, FieldType, Version};
+use crate::error::FieldWriteError;
+use crate::model::{Currency, Date, Timestamp};
+use crate::value::Value;
+
+const MAX_FIELD_NAME_LENGTH: usize = 10;
+const MAX_CHARACTER_LENGTH: usize = 254;
+const MAX_NUMERIC_LENGTH: usize = 20;
+// longer integers do not fit in an i64, so such columns are kept as text
+const MAX_INTEGRAL_LENGTH: usize = 18;
+const MAX_DECIMAL_COUNT: usize = 15;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TruncationPolicy {
+    Truncate,
+    Error,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverflowPolicy {
+    Null,
+    Error,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportOptions {
+    pub version: Version,
+    pub code_page: u8,
+    pub fields: Option<Vec<FieldInfo>>,
+    pub truncation: TruncationPolicy,
+    pub overflow: OverflowPolicy,
+    pub delimiter: u8,
+    pub encoding: Option<Encoding>,
+    pub infer_rows: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            version: Version::DBase3,
+            code_page: 0,
+            fields: None,
+            truncation: TruncationPolicy::Error,
+            overflow: OverflowPolicy::Error,
+            delimiter: b',',
+            encoding: None,
+            infer_rows: 1000,
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+#[derive(Debug)]
+enum Cell {
+    Null,
+    Bool(bool),
+    Number(String),
+    Text(String),
+}
+
+fn parse_logical(text: &str) -> Option<bool> {
+    match text.trim().to_ascii_lowercase().as_str() {
+        "t" | "true" | "y" | "yes" => Some(true),
+        "f" | "false" | "n" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim();
+    let is_numeric = !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == '.');
+
+    if is_numeric {
+        text.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn decimal_count(text: &str) -> usize {
+    text.trim()
+        .split('.')
+        .nth(1)
+        .map_or(0, |decimals| decimals.len())
+}
+
+fn parse_date(text: &str) -> Option<Date> {
+    let text = text.trim();
+    let bytes = text.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+
+    let year = text[0..4].parse().ok()?;
+    let month = text[5..7].parse().ok()?;
+    let day = text[8..10].parse().ok()?;
+    if month == 0 || month > 12 || day == 0 || day > 31 {
+        return None;
+    }
+
+    Some(Date::new(year, month, day))
+}
+
+fn parse_timestamp(text: &str) -> Option<Timestamp> {
+    let text = text.trim();
+    if text.len() < 16 || !text.is_char_boundary(10) {
+        return None;
+    }
+
+    let date = parse_date(&text[..10])?;
+    let separator = text.as_bytes()[10];
+    if separator != b'T' && separator != b' ' {
+        return None;
+    }
+
+    let mut time = text[11..].splitn(3, ':');
+    let hour = time.next()?.parse().ok()?;
+    let minute = time.next()?.parse().ok()?;
+    let (second, millisecond) = match time.next() {
+        Some(seconds) => {
+            let mut seconds = seconds.splitn(2, '.');
+            let second = seconds.next()?.parse().ok()?;
+            let millisecond = match seconds.next() {
+                Some(fraction) => format!("{:0<3}", fraction).get(..3)?.parse().ok()?,
+                None => 0,
+            };
+            (second, millisecond)
+        }
+        None => (0, 0),
+    };
+
+    if hour > 23 || minute > 59 || second > 59 {
+        return None;
+    }
+
+    Some(Timestamp::new(
+        date.year,
+        date.month,
+        date.day,
+        hour,
+        minute,
+        second,
+        millisecond,
+    ))
+}
+
+fn field_name(name: &str, used: &mut HashSet<String>) -> String {
+    let mut base = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .take(MAX_FIELD_NAME_LENGTH)
+        .collect::<String>();
+
+    if base.is_empty() {
+        base.push_str("FIELD");
+    }
+
+    let mut candidate = base.clone();
+    let mut suffix = 1;
+    while used.contains(&candidate) {
+        let suffix_text = suffix.to_string();
+        let prefix_length = base.len().min(MAX_FIELD_NAME_LENGTH - suffix_text.len());
+        candidate = format!("{}{}", &base[..prefix_length], suffix_text);
+        suffix += 1;
+    }
+
+    used.insert(candidate.clone());
+    candidate
+}
+
+fn infer_field(name: String, cells: &[&Cell], version: Version) -> FieldInfo {
+    let mut logical = true;
+    let mut numeric = true;
+    let mut date = true;
+    let mut timestamp = version == Version::VisualFoxPro;
+    let mut any_value = false;
+    let mut text_length = 1;
+    let mut integer_length = 1;
+    let mut decimals = 0;
+
+    for cell in cells {
+        let text = match cell {
+            Cell::Null => continue,
+            Cell::Bool(_) => {
+                any_value = true;
+                numeric = false;
+                date = false;
+                timestamp = false;
+                text_length = text_length.max(1);
+                continue;
+            }
+            Cell::Number(text) => {
+                logical = false;
+                date = false;
+                timestamp = false;
+                text
+            }
+            Cell::Text(text) => text,
+        };
+
+        any_value = true;
+        text_length = text_length.max(text.len());
+        logical = logical && parse_logical(text).is_some();
+        date = date && parse_date(text).is_some();
+        timestamp = timestamp && parse_timestamp(text).is_some();
+
+        if numeric && parse_number(text).is_some() {
+            let text = text.trim();
+            let places = decimal_count(text);
+            let integer_part = text.len() - if places > 0 { places + 1 } else { 0 };
+            integer_length = integer_length.max(integer_part);
+            decimals = decimals.max(places);
+        } else {
+            numeric = false;
+        }
+    }
+
+    let decimals = decimals.min(MAX_DECIMAL_COUNT);
+    let numeric_length = integer_length + if decimals > 0 { decimals + 1 } else { 0 };
+
+    if !any_value {
+        FieldInfo::with_type(name, FieldType::Character, 1, 0)
+    } else if logical {
+        FieldInfo::with_type(name, FieldType::Logical, 1, 0)
+    } else if numeric
+        && numeric_length <= MAX_NUMERIC_LENGTH
+        && (decimals > 0 || integer_length <= MAX_INTEGRAL_LENGTH)
+    {
+        FieldInfo::with_type(name, FieldType::Numeric, numeric_length, decimals)
+    } else if date {
+        FieldInfo::with_type(name, FieldType::Date, 8, 0)
+    } else if timestamp {
+        FieldInfo::with_type(name, FieldType::Timestamp, 8, 0)
+    } else {
+        let length = text_length.min(MAX_CHARACTER_LENGTH);
+        FieldInfo::with_type(name, FieldType::Character, length, 0)
+    }
+}
+
+fn infer_fields(columns: &[String], rows: &[Vec<Cell>], version: Version) -> Vec<FieldInfo> {
+    let mut used = HashSet::new();
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let cells = rows
+                .iter()
+                .map(|row| row.get(i).unwrap_or(&Cell::Null))
+                .collect::<Vec<_>>();
+            infer_field(field_name(column, &mut used), &cells, version)
+        })
+        .collect()
+}
+
+fn truncate(text: &str, length: usize) -> &str {
+    let mut end = length.min(text.len());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+fn convert_cell(
+    field: &FieldInfo,
+    cell: &Cell,
+    options: &ImportOptions,
+) -> Result<Value, FieldWriteError> {
+    let error =
+        |reason: &str| FieldWriteError::new(field.name.clone(), field.field_type.clone(), reason);
+
+    let text = match cell {
+        Cell::Null => return Ok(Value::Null),
+        Cell::Bool(val) => match field.field_type {
+            FieldType::Logical => return Ok(Value::Bool(*val)),
+            _ => (if *val { "T" } else { "F" }).to_owned(),
+        },
+        Cell::Number(text) | Cell::Text(text) => text.clone(),
+    };
+
+    match field.field_type {
+        FieldType::Character => {
+            if text.len() <= field.length {
+                Ok(Value::Str(text))
+            } else if options.truncation == TruncationPolicy::Truncate {
+                Ok(Value::Str(truncate(&text, field.length).to_owned()))
+            } else {
+                Err(error("value is longer than the field"))
+            }
+        }
+        FieldType::Logical => parse_logical(&text)
+            .map(Value::Bool)
+            .ok_or_else(|| error("value is not a logical")),
+        FieldType::Numeric | FieldType::Float => {
+            let value = parse_number(&text).ok_or_else(|| error("value is not a number"))?;
+            // integers keep their digits as i64, and one that overflows it does not fit
+            let is_integral = field.field_type == FieldType::Numeric
+                && field.decimal_count == 0
+                && decimal_count(&text) == 0;
+            let value = if is_integral {
+                text.trim()
+                    .parse::<i64>()
+                    .ok()
+                    .filter(|value| value.to_string().len() <= field.length)
+                    .map(Value::Int)
+            } else {
+                Some(value)
+                    .filter(|value| {
+                        format!("{:.*}", field.decimal_count, value).len() <= field.length
+                    })
+                    .map(Value::Float)
+            };
+            match value {
+                Some(value) => Ok(value),
+                None if options.overflow == OverflowPolicy::Null => Ok(Value::Null),
+                None => Err(error("value does not fit in the field")),
+            }
+        }
+        FieldType::Integer => match parse_number(&text) {
+            Some(value) if value.fract() == 0.0 => {
+                if value >= i32::MIN as f64 && value <= i32::MAX as f64 {
+                    Ok(Value::Int(value as i64))
+                } else if options.overflow == OverflowPolicy::Null {
+                    Ok(Value::Null)
+                } else {
+                    Err(error("value does not fit in the field"))
+                }
+            }
+            _ => Err(error("value is not an integer")),
+        },
+        FieldType::Double => parse_number(&text)
+            .map(Value::Float)
+            .ok_or_else(|| error("value is not a number")),
+        FieldType::Currency => text
+            .trim()
+            .parse::<Currency>()
+            .map(Value::Currency)
+            .map_err(|_| error("value is not a currency amount")),
+        FieldType::Date => parse_date(&text)
+            .map(Value::Date)
+            .ok_or_else(|| error("value is not a date")),
+        FieldType::Timestamp => parse_timestamp(&text)
+            .map(Value::Timestamp)
+            .ok_or_else(|| error("value is not a timestamp")),
+        FieldType::Memo | FieldType::Binary | FieldType::General => {
+            Err(error("memo fields are not supported"))
+        }
+        FieldType::Varchar | FieldType::Varbinary => {
+            Err(error("variable length fields are not supported"))
+        }
+    }
+}
+
+// field types are inferred from the first rows, the remaining rows are streamed
+fn write_rows<I, W>(
+    columns: &[String],
+    mut rows: I,
+    writer: W,
+    options: &ImportOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    I: Iterator<Item = Result<Vec<Cell>, Box<dyn std::error::Error>>>,
+    W: Write + Seek,
+{
+    let sample = match &options.fields {
+        Some(_) => Vec::new(),
+        None => rows
+            .by_ref()
+            .take(options.infer_rows.max(1))
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    let fields = match &options.fields {
+        Some(fields) => fields.clone(),
+        None => infer_fields(columns, &sample, options.version),
+    };
+
+    let column_indexes = match &options.fields {
+        Some(fields) => fields
+            .iter()
+            .map(|f| columns.iter().position(|c| c.eq_ignore_ascii_case(&f.name)))
+            .collect::<Vec<_>>(),
+        None => (0..fields.len()).map(Some).collect(),
+    };
+
+    let mut writer = DbfWriter::new(writer, options.version, options.code_page, fields)?;
+    if let Some(encoding) = options.encoding {
+        writer.set_encoding(encoding);
+    }
+    let mut values = Vec::new();
+
+    for row in sample.into_iter().map(Ok).chain(rows) {
+        let row = row?;
+        values.clear();
+        for (field, index) in writer.fields().iter().zip(&column_indexes) {
+            let cell = index.and_then(|i| row.get(i)).unwrap_or(&Cell::Null);
+            values.push(convert_cell(field, cell, options)?);
+        }
+        writer.write_record(&values)?;
+    }
+
+    let record_count = writer.record_count() as usize;
+    writer.finish()?;
+    Ok(record_count)
+}
+
+#[cfg(feature = "csv")]
+pub fn import_csv<R, W>(
+    reader: R,
+    writer: W,
+    options: &ImportOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: std::io::Read,
+    W: Write + Seek,
+{
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(options.delimiter)
+        .flexible(true)
+        .from_reader(reader);
+
+    let columns = reader
+        .headers()?
+        .iter()
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+
+    let rows = reader.into_records().map(|record| {
+        let row = record?
+            .iter()
+            .map(|cell| {
+                if cell.is_empty() {
+                    Cell::Null
+                } else {
+                    Cell::Text(cell.to_owned())
+                }
+            })
+            .collect();
+        Ok(row)
+    });
+
+    write_rows(&columns, rows, writer, options)
+}
+
+#[cfg(feature = "json")]
+pub fn import_ndjson<R, W>(
+    reader: R,
+    writer: W,
+    options: &ImportOptions,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    R: std::io::BufRead,
+    W: Write + Seek,
+{
+    use serde_json::{Map, Value as JsonValue};
+
+    let mut lines = reader.lines().filter(|line| match line {
+        Ok(line) => !line.trim().is_empty(),
+        Err(_) => true,
+    });
+    let parse = |line: std::io::Result<String>| -> Result<_, Box<dyn std::error::Error>> {
+        let object: Map<String, JsonValue> = serde_json::from_str(&line?)?;
+        Ok(object)
+    };
+
+    // the columns are the keys found in the rows used to infer the fields
+    let sample = lines
+        .by_ref()
+        .take(options.infer_rows.max(1))
+        .map(parse)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut columns: Vec<String> = Vec::new();
+    let keys = sample.iter().flat_map(|object| object.keys());
+    let field_names = options.fields.iter().flatten().map(|f| &f.name);
+    for key in keys.chain(field_names) {
+        if !columns.contains(key) {
+            columns.push(key.clone());
+        }
+    }
+
+    let to_row = |mut object: Map<String, JsonValue>| -> Vec<Cell> {
+        columns
+            .iter()
+            .map(|column| match object.remove(column) {
+                None | Some(JsonValue::Null) => Cell::Null,
+                Some(JsonValue::Bool(val)) => Cell::Bool(val),
+                Some(JsonValue::Number(val)) => Cell::Number(val.to_string()),
+                Some(JsonValue::String(val)) => Cell::Text(val),
+                Some(val) => Cell::Text(val.to_string()),
+            })
+            .collect()
+    };
+    let rows = sample
+        .into_iter()
+        .map(Ok)
+        .chain(lines.map(parse))
+        .map(|object| object.map(to_row));
+
+    write_rows(&columns, rows, writer, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::DbfReader;
+    use crate::record::DynamicRecordIterator;
+
+    fn read(mut table: Cursor<Vec<u8>>) -> (Vec<FieldInfo>, Vec<Vec<Value>>) {
+        table.set_position(0);
+        let records = DynamicRecordIterator::new(DbfReader::from_reader(table, None).unwrap());
+        let fields = records.fields().to_vec();
+        let values = records.map(|r| r.unwrap().into_values()).collect();
+        (fields, values)
+    }
+
+    #[cfg(feature = "csv")]
+    fn import(csv: &str, options: &ImportOptions) -> (Vec<FieldInfo>, Vec<Vec<Value>>) {
+        let mut table = Cursor::new(Vec::new());
+        import_csv(csv.as_bytes(), &mut table, options).unwrap();
+        read(table)
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_round_trip() {
+        let csv = "name,qty,price,active,since\nSão Paulo,3,12.50,yes,2024-02-29\nOslo,,7.25,no,\n";
+        let (fields, records) = import(csv, &ImportOptions::default());
+
+        let types = fields
+            .iter()
+            .map(|f| {
+                (
+                    f.name.as_str(),
+                    f.field_type.clone(),
+                    f.length,
+                    f.decimal_count,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                ("NAME", FieldType::Character, 10, 0),
+                ("QTY", FieldType::Numeric, 1, 0),
+                ("PRICE", FieldType::Numeric, 5, 2),
+                ("ACTIVE", FieldType::Logical, 1, 0),
+                ("SINCE", FieldType::Date, 8, 0),
+            ]
+        );
+        assert_eq!(
+            records,
+            vec![
+                vec![
+                    Value::Str("São Paulo".to_owned()),
+                    Value::Int(3),
+                    Value::Float(12.5),
+                    Value::Bool(true),
+                    Value::Date(Date::new(2024, 2, 29)),
+                ],
+                vec![
+                    Value::Str("Oslo".to_owned()),
+                    Value::Null,
+                    Value::Float(7.25),
+                    Value::Bool(false),
+                    Value::Null,
+                ],
+            ]
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn integers_keep_their_digits() {
+        let csv = "id,big\n123456789012345678,1234567890123456789\n-5,12345678901234567890\n";
+        let (fields, records) = import(csv, &ImportOptions::default());
+
+        assert_eq!(fields[0].field_type, FieldType::Numeric);
+        assert_eq!((fields[0].length, fields[0].decimal_count), (18, 0));
+        // more than 18 digits do not fit in an i64, so the column is kept as text
+        assert_eq!(fields[1].field_type, FieldType::Character);
+        assert_eq!(
+            records[0],
+            vec![
+                Value::Int(123_456_789_012_345_678),
+                Value::Str("1234567890123456789".to_owned())
+            ]
+        );
+        assert_eq!(
+            records[1],
+            vec![
+                Value::Int(-5),
+                Value::Str("12345678901234567890".to_owned())
+            ]
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn integers_overflowing_i64_do_not_fit_numeric_fields() {
+        let mut options = ImportOptions {
+            fields: Some(vec![FieldInfo::with_type("ID", FieldType::Numeric, 20, 0)]),
+            ..Default::default()
+        };
+        let mut table = Cursor::new(Vec::new());
+        let error = import_csv(&b"id\n12345678901234567890\n"[..], &mut table, &options);
+        assert_eq!(
+            error.unwrap_err().to_string(),
+            "Field 'ID' (Numeric) could not be written: value does not fit in the field"
+        );
+
+        options.overflow = OverflowPolicy::Null;
+        let (_, records) = import("id\n12345678901234567890\n42\n", &options);
+        assert_eq!(records, vec![vec![Value::Null], vec![Value::Float(42.0)]]);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn ndjson_round_trip() {
+        let ndjson =
+            "{\"id\":1,\"name\":\"Zoë\",\"ok\":true}\n\n{\"id\":2,\"name\":null,\"extra\":1.5}\n";
+        let mut table = Cursor::new(Vec::new());
+        let count =
+            import_ndjson(ndjson.as_bytes(), &mut table, &ImportOptions::default()).unwrap();
+        let (fields, values) = read(table);
+        let names = fields.iter().map(|f| f.name.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(count, 2);
+        assert_eq!(names, vec!["ID", "NAME", "OK", "EXTRA"]);
+        assert_eq!(
+            values,
+            vec![
+                vec![
+                    Value::Int(1),
+                    Value::Str("Zoë".to_owned()),
+                    Value::Bool(true),
+                    Value::Null
+                ],
+                vec![Value::Int(2), Value::Null, Value::Null, Value::Float(1.5)],
+            ]
+        );
+    }
+}
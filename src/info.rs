@@ -0,0 +1,106 @@
+use std::io::{Read, Seek};
+
+use serde::Serialize;
+
+use crate::dbf::{DbfReader, FieldInfo, FieldProperty, Version};
+use crate::model::Date;
+
+// https://www.clicketyclick.dk/databases/xbase/format/dbf.html#DBF_NOTE_5_TARGET
+const CODE_PAGES: [(u8, u16, &str); 25] = [
+    (0x01, 437, "US MS-DOS"),
+    (0x02, 850, "International MS-DOS"),
+    (0x03, 1252, "Windows ANSI"),
+    (0x04, 10000, "Standard Macintosh"),
+    (0x57, 1252, "ANSI"),
+    (0x64, 852, "Eastern European MS-DOS"),
+    (0x65, 866, "Russian MS-DOS"),
+    (0x66, 865, "Nordic MS-DOS"),
+    (0x67, 861, "Icelandic MS-DOS"),
+    (0x6a, 737, "Greek MS-DOS"),
+    (0x6b, 857, "Turkish MS-DOS"),
+    (0x78, 950, "Traditional Chinese Windows"),
+    (0x79, 949, "Korean Windows"),
+    (0x7a, 936, "Chinese Simplified Windows"),
+    (0x7b, 932, "Japanese Windows"),
+    (0x7c, 874, "Thai Windows"),
+    (0x7d, 1255, "Hebrew Windows"),
+    (0x7e, 1256, "Arabic Windows"),
+    (0x96, 10007, "Russian Macintosh"),
+    (0x97, 10029, "Macintosh EE"),
+    (0x98, 10006, "Greek Macintosh"),
+    (0xc8, 1250, "Eastern European Windows"),
+    (0xc9, 1251, "Russian Windows"),
+    (0xca, 1254, "Turkish Windows"),
+    (0xcb, 1253, "Greek Windows"),
+];
+
+pub fn code_page(code_page_mark: u8) -> Option<(u16, &'static str)> {
+    CODE_PAGES
+        .iter()
+        .find(|(mark, _, _)| *mark == code_page_mark)
+        .map(|(_, code_page, name)| (*code_page, *name))
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct MemoInfo {
+    pub block_size: u16,
+    pub next_block: u32,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TableInfo {
+    pub version: Version,
+    pub last_update: Date,
+    pub record_count: u32,
+    pub deleted_count: u32,
+    pub header_length: usize,
+    pub record_length: usize,
+    pub code_page: u8,
+    pub language_driver: Option<String>,
+    pub fields: Vec<FieldInfo>,
+    pub properties: Vec<FieldProperty>,
+    pub memo: Option<MemoInfo>,
+}
+
+impl TableInfo {
+    pub fn from_reader<R: Read + Seek>(
+        mut reader: DbfReader<R>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let header = reader.header();
+        let version = header.version;
+        let last_update = header.last_update.clone();
+        let record_count = header.record_count;
+        let header_length = header.header_length;
+        let record_length = header.record_length;
+        let code_page = header.code_page;
+        let language_driver = header.language_driver.clone();
+        let fields = reader.fields().clone();
+        let properties = reader.properties().to_vec();
+        let memo = reader.memo_reader().map(|m| MemoInfo {
+            block_size: m.block_size(),
+            next_block: m.next_block(),
+        });
+
+        reader.set_include_deleted(true);
+        let mut deleted_count = 0;
+        while let Some(record) = reader.next_raw_record()? {
+            if record.is_deleted() {
+                deleted_count += 1;
+            }
+        }
+
+        Ok(Self {
+            version,
+            last_update,
+            record_count,
+            deleted_count,
+            header_length,
+            record_length,
+            code_page,
+            language_driver,
+            fields,
+            properties,
+            memo,
+        })
+    }
+}
@@ -1,23 +1,172 @@
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{Read, Seek};
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
 
+mod batch;
 mod dbf;
 mod deserialize;
+mod diff;
 mod error;
+mod export;
+mod filter;
+#[cfg(any(feature = "csv", feature = "json"))]
+mod import;
+mod info;
 mod model;
+mod record;
+mod validate;
 mod value;
 
 use dbf::DbfReader;
 use deserialize::DbfDeserializer;
 
-pub use model::{Date, Timestamp};
+pub use batch::{Batch, BatchIterator, Column};
+pub use dbf::{
+    Autoincrement, DbfWriter, Encoding, FieldFlags, FieldInfo, FieldProperty, FieldType, Header,
+    PropertyKind, Version,
+};
+pub use diff::{FieldChange, RecordChange, SchemaDiff, TableDiff, ValueChange};
+#[cfg(feature = "arrow")]
+pub use export::arrow::{arrow_schema, to_record_batch, ArrowBatchIterator};
+#[cfg(feature = "csv")]
+pub use export::csv::{write_csv, CsvOptions, CsvWriter, QuoteStyle as CsvQuoteStyle};
+#[cfg(feature = "datafusion")]
+pub use export::datafusion::{
+    enable_dbf_url_table, register_dbf, register_dbf_directory, DbfTable, DbfUrlTableFactory,
+};
+#[cfg(feature = "json")]
+pub use export::json::{write_json, write_json_records, JsonFormat, JsonOptions, JsonWriter};
+#[cfg(feature = "parquet")]
+pub use export::parquet::{
+    convert_to_parquet, write_parquet, Compression as ParquetCompression, ParquetOptions,
+};
+#[cfg(feature = "polars")]
+pub use export::polars::{read_dataframe, to_dataframe, DataFrameOptions};
+pub use export::sql::{create_table_statement, sql_column_type, write_pg_copy, SqlDialect};
+#[cfg(feature = "sqlite")]
+pub use export::sqlite::{
+    create_table_sql as sqlite_create_table_sql, import_records as import_sqlite_records,
+    import_sqlite, import_sqlite_directory, SqliteOptions,
+};
+#[cfg(any(feature = "csv", feature = "json"))]
+pub use export::BinaryEncoding;
+pub use filter::Filter;
+#[cfg(feature = "csv")]
+pub use import::import_csv;
+#[cfg(feature = "json")]
+pub use import::import_ndjson;
+#[cfg(any(feature = "csv", feature = "json"))]
+pub use import::{ImportOptions, OverflowPolicy, TruncationPolicy};
+pub use info::{code_page, MemoInfo, TableInfo};
+pub use model::{Currency, Date, Timestamp};
+pub use record::{DynamicRecordIterator, Record};
+pub use validate::{Issue, ValidationReport};
 pub use value::Value;
 
+const MEMO_EXTENSIONS: [&str; 2] = ["dbt", "fpt"];
+
+// bare file names are relative to the current directory
+fn table_directory(table_path: &Path) -> &Path {
+    table_path
+        .parent()
+        .filter(|directory| !directory.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+}
+
+// the extension of the memo file written by the program the table version belongs to
+fn memo_extension(table_path: &Path) -> Option<&'static str> {
+    let mut signature = [0u8; 1];
+    File::open(table_path)
+        .and_then(|mut table| table.read_exact(&mut signature))
+        .ok()?;
+
+    match Version::try_from(signature[0]).ok()? {
+        Version::DBase3 | Version::DBase4 | Version::DBase7 => Some("dbt"),
+        Version::FoxBase | Version::FoxPro2 | Version::VisualFoxPro => Some("fpt"),
+    }
+}
+
+pub fn find_memo_file<P: AsRef<Path>>(table_path: P) -> Option<PathBuf> {
+    let table_path = table_path.as_ref();
+    let stem = table_path.file_stem()?;
+
+    let mut memo_paths = std::fs::read_dir(table_directory(table_path))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_stem()
+                .is_some_and(|memo_stem| memo_stem.eq_ignore_ascii_case(stem))
+                && path.extension().is_some_and(|ext| {
+                    MEMO_EXTENSIONS
+                        .iter()
+                        .any(|memo_ext| ext.eq_ignore_ascii_case(memo_ext))
+                })
+        })
+        .collect::<Vec<_>>();
+    memo_paths.sort();
+
+    let preferred = memo_extension(table_path).and_then(|memo_ext| {
+        memo_paths.iter().position(|path| {
+            path.extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case(memo_ext))
+        })
+    });
+    match preferred {
+        Some(i) => Some(memo_paths.swap_remove(i)),
+        None => memo_paths.into_iter().next(),
+    }
+}
+
+pub fn table_info<P>(
+    table_path: P,
+    memo_path: Option<P>,
+) -> Result<TableInfo, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let table_file = File::open(table_path.as_ref())?;
+    let memo_file = memo_path.map(File::open).transpose()?;
+    DbfReader::from_reader(table_file, memo_file).and_then(TableInfo::from_reader)
+}
+
+pub fn validate<P>(
+    table_path: P,
+    memo_path: Option<P>,
+) -> Result<ValidationReport, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let table_file = File::open(table_path.as_ref())?;
+    let memo_file = memo_path.map(File::open).transpose()?;
+    ValidationReport::from_reader(table_file, memo_file)
+}
+
+// memo files are looked up next to both tables
+pub fn diff<P>(
+    old_path: P,
+    new_path: P,
+    key: &[String],
+) -> Result<TableDiff, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let old = read_records(
+        old_path.as_ref(),
+        find_memo_file(old_path.as_ref()).as_deref(),
+    )?;
+    let new = read_records(
+        new_path.as_ref(),
+        find_memo_file(new_path.as_ref()).as_deref(),
+    )?;
+    TableDiff::from_records(old, new, key)
+}
+
 pub fn read<P, T>(
     table_path: P,
     memo_path: Option<P>,
@@ -43,6 +192,44 @@ where
     DbfReader::from_reader(table_file, memo_file).map(|r| ValuesIterator { reader: r })
 }
 
+pub fn read_records<P>(
+    table_path: P,
+    memo_path: Option<P>,
+) -> Result<DynamicRecordIterator<File>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let table_file = File::open(table_path.as_ref())?;
+    let memo_file = memo_path.map(File::open).transpose()?;
+    DbfReader::from_reader(table_file, memo_file).map(DynamicRecordIterator::new)
+}
+
+pub fn read_batches<P>(
+    table_path: P,
+    memo_path: Option<P>,
+    batch_size: usize,
+) -> Result<BatchIterator<File>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    let table_file = File::open(table_path.as_ref())?;
+    let memo_file = memo_path.map(File::open).transpose()?;
+    let reader = DbfReader::from_reader(table_file, memo_file)?;
+    BatchIterator::new(reader, batch_size).map_err(From::from)
+}
+
+#[cfg(feature = "arrow")]
+pub fn read_arrow<P>(
+    table_path: P,
+    memo_path: Option<P>,
+    batch_size: usize,
+) -> Result<ArrowBatchIterator<File>, Box<dyn std::error::Error>>
+where
+    P: AsRef<Path>,
+{
+    read_batches(table_path, memo_path, batch_size).map(ArrowBatchIterator::new)
+}
+
 pub struct RecordIterator<R, T>
 where
     R: Read + Seek,
@@ -89,6 +276,12 @@ where
     reader: DbfReader<R>,
 }
 
+impl<R: Read + Seek> ValuesIterator<R> {
+    pub fn fields(&self) -> &[FieldInfo] {
+        self.reader.fields()
+    }
+}
+
 impl<R: Read + Seek> Iterator for ValuesIterator<R> {
     type Item = Result<BTreeMap<String, Value>, Box<dyn std::error::Error>>;
 
@@ -105,3 +298,53 @@ impl<R: Read + Seek> Iterator for ValuesIterator<R> {
         Some(Ok(key_iter.zip(val_iter).collect()))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn memo_directory(name: &str) -> PathBuf {
+        let directory = std::env::temp_dir().join(format!("dbf-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        directory
+    }
+
+    #[test]
+    fn bare_file_names_are_in_the_current_directory() {
+        assert_eq!(table_directory(Path::new("table.dbf")), Path::new("."));
+        assert_eq!(
+            table_directory(Path::new("data/table.dbf")),
+            Path::new("data")
+        );
+    }
+
+    #[test]
+    fn find_memo_file_ignores_case() {
+        let directory = memo_directory("memo-case");
+        fs::write(directory.join("TABLE.DBF"), [0x30]).unwrap();
+        fs::write(directory.join("table.fpt"), b"").unwrap();
+
+        let memo_path = find_memo_file(directory.join("TABLE.DBF"));
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(memo_path.unwrap().file_name().unwrap(), "table.fpt");
+    }
+
+    #[test]
+    fn find_memo_file_prefers_the_memo_type_of_the_version() {
+        let directory = memo_directory("memo-version");
+        fs::write(directory.join("table.dbt"), b"").unwrap();
+        fs::write(directory.join("table.FPT"), b"").unwrap();
+
+        fs::write(directory.join("table.dbf"), [0x30]).unwrap();
+        let visual_foxpro = find_memo_file(directory.join("table.dbf"));
+        fs::write(directory.join("table.dbf"), [0x83]).unwrap();
+        let dbase3 = find_memo_file(directory.join("table.dbf"));
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(visual_foxpro.unwrap().file_name().unwrap(), "table.FPT");
+        assert_eq!(dbase3.unwrap().file_name().unwrap(), "table.dbt");
+    }
+}
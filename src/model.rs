@@ -1,10 +1,13 @@
-use std::fmt;
+use std::fmt::{self, Write};
+use std::str::FromStr;
 use std::string::ToString;
 
 use serde::de::{self, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Clone, Debug)]
+use crate::error::ParseCurrencyError;
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Date {
     pub year: u16,
     pub month: u8,
@@ -15,6 +18,55 @@ impl Date {
     pub fn new(year: u16, month: u8, day: u8) -> Self {
         Self { year, month, day }
     }
+
+    // https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    pub fn days_since_epoch(&self) -> i32 {
+        let month = self.month as i32;
+        let day = self.day as i32;
+        let year = self.year as i32 - if month <= 2 { 1 } else { 0 };
+
+        let era = (if year >= 0 { year } else { year - 399 }) / 400;
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    pub fn from_days_since_epoch(days: i32) -> Self {
+        let days = days + 719_468;
+        let era = (if days >= 0 { days } else { days - 146_096 }) / 146_097;
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Self::new(year as u16, month as u8, day as u8)
+    }
+
+    pub fn today() -> Self {
+        let days = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() / 86_400)
+            .unwrap_or(0);
+        Self::from_days_since_epoch(days as i32)
+    }
+
+    pub fn format(&self, pattern: &str) -> String {
+        format_pattern(pattern, self, &Time::new(0, 0, 0, 0))
+    }
+
+    // days past the end of the month would roll over into the next one
+    pub(crate) fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && Self::from_days_since_epoch(self.days_since_epoch()) == *self
+    }
 }
 
 impl fmt::Display for Date {
@@ -66,7 +118,7 @@ impl<'de> Deserialize<'de> for Date {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Time {
     pub hour: u8,
     pub minute: u8,
@@ -76,29 +128,64 @@ pub struct Time {
 
 impl Time {
     pub fn new(hour: u8, minute: u8, second: u8, millisecond: u16) -> Self {
-        Self { hour, minute, second, millisecond }
+        Self {
+            hour,
+            minute,
+            second,
+            millisecond,
+        }
+    }
+
+    pub fn milliseconds_since_midnight(&self) -> i64 {
+        ((self.hour as i64 * 60 + self.minute as i64) * 60 + self.second as i64) * 1000
+            + self.millisecond as i64
     }
 }
 
 impl fmt::Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{:02}:{:02}:{:02}.{:03}", self.hour, self.minute, self.second, self.millisecond)
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03}",
+            self.hour, self.minute, self.second, self.millisecond
+        )
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Timestamp {
     pub date: Date,
     pub time: Time,
 }
 
 impl Timestamp {
-    pub(crate) fn new(year: u16, month: u8, day: u8, hour: u8, minute: u8, second: u8, millisecond: u16) -> Self {
+    pub(crate) fn new(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+        millisecond: u16,
+    ) -> Self {
         Self {
             date: Date { year, month, day },
-            time: Time { hour, minute, second, millisecond },
+            time: Time {
+                hour,
+                minute,
+                second,
+                millisecond,
+            },
         }
     }
+
+    pub fn milliseconds_since_epoch(&self) -> i64 {
+        self.date.days_since_epoch() as i64 * 86_400_000 + self.time.milliseconds_since_midnight()
+    }
+
+    pub fn format(&self, pattern: &str) -> String {
+        format_pattern(pattern, &self.date, &self.time)
+    }
 }
 
 impl fmt::Display for Timestamp {
@@ -156,9 +243,142 @@ impl<'de> Deserialize<'de> for Timestamp {
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(6, &self))?;
 
-                Ok(Timestamp::new(year, month, day, hour, minute, second, millisecond))
+                Ok(Timestamp::new(
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    millisecond,
+                ))
             }
         }
         deserializer.deserialize_seq(TimestampVisitor)
     }
 }
+
+// Supports the strftime specifiers %Y, %m, %d, %H, %M, %S, %f (milliseconds) and %%
+fn format_pattern(pattern: &str, date: &Date, time: &Time) -> String {
+    let mut output = String::with_capacity(pattern.len() + 8);
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+
+        let _ = match chars.next() {
+            Some('Y') => write!(output, "{:04}", date.year),
+            Some('m') => write!(output, "{:02}", date.month),
+            Some('d') => write!(output, "{:02}", date.day),
+            Some('H') => write!(output, "{:02}", time.hour),
+            Some('M') => write!(output, "{:02}", time.minute),
+            Some('S') => write!(output, "{:02}", time.second),
+            Some('f') => write!(output, "{:03}", time.millisecond),
+            Some('%') => write!(output, "%"),
+            Some(other) => write!(output, "%{}", other),
+            None => write!(output, "%"),
+        };
+    }
+
+    output
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency {
+    pub units: i64,
+}
+
+impl Currency {
+    pub const SCALE: i64 = 10_000;
+
+    pub fn new(units: i64) -> Self {
+        Self { units }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        self.units as f64 / Self::SCALE as f64
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.units < 0 { "-" } else { "" };
+        let units = self.units.unsigned_abs();
+        let scale = Self::SCALE as u64;
+        write!(f, "{}{}.{:04}", sign, units / scale, units % scale)
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ParseCurrencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseCurrencyError(s.to_owned());
+        let text = s.trim();
+        let (negative, text) = match text.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+
+        let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() && fraction.is_empty()
+            || !digits(whole)
+            || !digits(fraction)
+            || fraction.len() > 4
+        {
+            return Err(error());
+        }
+
+        let whole = match whole {
+            "" => 0,
+            whole => whole.parse::<i64>().map_err(|_| error())?,
+        };
+        let fraction = format!("{:0<4}", fraction)
+            .parse::<i64>()
+            .map_err(|_| error())?;
+        let units = whole
+            .checked_mul(Self::SCALE)
+            .and_then(|units| units.checked_add(fraction))
+            .ok_or_else(error)?;
+
+        Ok(Self::new(if negative { -units } else { units }))
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct CurrencyVisitor;
+
+        impl<'de> Visitor<'de> for CurrencyVisitor {
+            type Value = Currency;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a decimal number with up to 4 decimal places")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Currency, E>
+            where
+                E: de::Error,
+            {
+                value.parse().map_err(E::custom)
+            }
+        }
+        deserializer.deserialize_str(CurrencyVisitor)
+    }
+}
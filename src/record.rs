@@ -0,0 +1,325 @@
+use std::io::{Read, Seek};
+use std::ops::Index;
+use std::sync::Arc;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use crate::dbf::{DbfReader, Encoding, FieldInfo};
+use crate::error::NoSuchFieldError;
+use crate::filter::Filter;
+use crate::value::Value;
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    fields: Arc<[FieldInfo]>,
+    values: Vec<Value>,
+    record_number: u32,
+    deleted: bool,
+}
+
+impl Record {
+    pub(crate) fn new(
+        fields: Arc<[FieldInfo]>,
+        values: Vec<Value>,
+        record_number: u32,
+        deleted: bool,
+    ) -> Self {
+        Self {
+            fields,
+            values,
+            record_number,
+            deleted,
+        }
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    pub fn record_number(&self) -> u32 {
+        self.record_number
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.fields
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.values.get(index)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&Value> {
+        self.position(name).map(|i| &self.values[i])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .zip(self.values.iter())
+    }
+
+    pub fn into_values(self) -> Vec<Value> {
+        self.values
+    }
+}
+
+impl Index<usize> for Record {
+    type Output = Value;
+
+    fn index(&self, index: usize) -> &Value {
+        &self.values[index]
+    }
+}
+
+impl Index<&str> for Record {
+    type Output = Value;
+
+    fn index(&self, name: &str) -> &Value {
+        match self.get_by_name(name) {
+            Some(value) => value,
+            None => panic!("Field '{}' does not exist", name),
+        }
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.values.len()))?;
+        for (name, value) in self.iter() {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+pub struct DynamicRecordIterator<R>
+where
+    R: Read + Seek,
+{
+    reader: DbfReader<R>,
+    table_fields: Arc<[FieldInfo]>,
+    fields: Arc<[FieldInfo]>,
+    projection: Vec<usize>,
+    filter: Option<Filter>,
+}
+
+impl<R: Read + Seek> DynamicRecordIterator<R> {
+    pub fn new(reader: DbfReader<R>) -> Self {
+        let fields: Arc<[FieldInfo]> = reader.fields().clone().into();
+        let projection = (0..reader.fields().len()).collect();
+        Self {
+            reader,
+            table_fields: fields.clone(),
+            fields,
+            projection,
+            filter: None,
+        }
+    }
+
+    pub fn fields(&self) -> &[FieldInfo] {
+        &self.fields
+    }
+
+    pub fn set_include_deleted(&mut self, include_deleted: bool) {
+        self.reader.set_include_deleted(include_deleted);
+    }
+
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.reader.set_encoding(encoding);
+    }
+
+    /// Only yields the records matching the filter, which is evaluated before the projection.
+    pub fn set_filter(&mut self, filter: Filter) {
+        self.filter = Some(filter);
+    }
+
+    pub fn set_projection<S: AsRef<str>>(&mut self, columns: &[S]) -> Result<(), NoSuchFieldError> {
+        let fields = self.reader.fields();
+        self.projection = columns
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                fields
+                    .iter()
+                    .position(|f| f.name.eq_ignore_ascii_case(name))
+                    .ok_or_else(|| NoSuchFieldError::new(name))
+            })
+            .collect::<Result<_, _>>()?;
+        self.fields = self.projection.iter().map(|i| fields[*i].clone()).collect();
+
+        Ok(())
+    }
+
+    fn next_record(&mut self) -> Result<Option<Record>, Box<dyn std::error::Error>> {
+        loop {
+            let mut record = match self.reader.next_raw_record()? {
+                Some(record) => record,
+                None => return Ok(None),
+            };
+            let record_number = record.record_number();
+            let deleted = record.is_deleted();
+
+            let filter = match &self.filter {
+                Some(filter) => filter,
+                None => {
+                    let values = self
+                        .projection
+                        .iter()
+                        .map(|i| record.parse_field(*i).map(Value::from))
+                        .collect::<Result<_, _>>()?;
+
+                    return Ok(Some(Record::new(
+                        self.fields.clone(),
+                        values,
+                        record_number,
+                        deleted,
+                    )));
+                }
+            };
+
+            let values = (0..self.table_fields.len())
+                .map(|i| record.parse_field(i).map(Value::from))
+                .collect::<Result<_, _>>()?;
+            let table_record =
+                Record::new(self.table_fields.clone(), values, record_number, deleted);
+            if !filter.matches(&table_record)? {
+                continue;
+            }
+
+            let values = self
+                .projection
+                .iter()
+                .map(|i| table_record.values()[*i].clone())
+                .collect();
+
+            return Ok(Some(Record::new(
+                self.fields.clone(),
+                values,
+                record_number,
+                deleted,
+            )));
+        }
+    }
+}
+
+impl<R: Read + Seek> Iterator for DynamicRecordIterator<R> {
+    type Item = Result<Record, Box<dyn std::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfWriter, FieldType, Version};
+
+    fn records() -> DynamicRecordIterator<Cursor<Vec<u8>>> {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+            FieldInfo::with_type("STATE", FieldType::Character, 2, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 5, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        for (name, state, qty) in [("Ana", "SP", 1), ("Bruno", "RJ", 2), ("Carla", "SP", 3)] {
+            let values = [
+                Value::Str(name.to_owned()),
+                Value::Str(state.to_owned()),
+                Value::Int(qty),
+            ];
+            writer.write_record(&values).unwrap();
+        }
+        let mut table = writer.finish().unwrap().into_inner();
+        // the second record is marked as deleted
+        let header_length = u16::from_le_bytes([table[8], table[9]]) as usize;
+        let record_length = u16::from_le_bytes([table[10], table[11]]) as usize;
+        table[header_length + record_length] = b'*';
+
+        DynamicRecordIterator::new(DbfReader::from_reader(Cursor::new(table), None).unwrap())
+    }
+
+    #[test]
+    fn record_accessors() {
+        let record = records().next().unwrap().unwrap();
+
+        assert_eq!((record.record_number(), record.is_deleted()), (1, false));
+        assert_eq!(record.len(), 3);
+        assert_eq!(record.position("qty"), Some(2));
+        assert_eq!(
+            record.get_by_name("state"),
+            Some(&Value::Str("SP".to_owned()))
+        );
+        assert_eq!(record.get_by_name("city"), None);
+        assert_eq!(record["Name"], Value::Str("Ana".to_owned()));
+        assert_eq!(record[2], Value::Int(1));
+        let names = record.iter().map(|(name, _)| name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["NAME", "STATE", "QTY"]);
+    }
+
+    #[test]
+    fn deleted_records() {
+        let numbers = |records: DynamicRecordIterator<_>| {
+            records
+                .map(|r| r.unwrap())
+                .map(|r| (r.record_number(), r.is_deleted()))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(records()), vec![(1, false), (3, false)]);
+        let mut all = records();
+        all.set_include_deleted(true);
+        assert_eq!(numbers(all), vec![(1, false), (2, true), (3, false)]);
+    }
+
+    #[test]
+    fn projection_and_filter() {
+        let mut records = records();
+        assert!(records.set_projection(&["city"]).is_err());
+        records.set_projection(&["qty", "name"]).unwrap();
+        // the filter sees the fields left out of the projection
+        records.set_filter(Filter::parse("STATE = 'SP'").unwrap());
+
+        let fields = records
+            .fields()
+            .iter()
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(fields, vec!["QTY", "NAME"]);
+        let values = records
+            .map(|r| r.unwrap().into_values())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                vec![Value::Int(1), Value::Str("Ana".to_owned())],
+                vec![Value::Int(3), Value::Str("Carla".to_owned())],
+            ]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Read, Seek, SeekFrom};
+
+use serde::Serialize;
+
+use crate::dbf::{field_descriptor_length, DbfReader};
+
+const EOF_MARKER: u8 = 0x1a;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Issue {
+    HeaderLength {
+        expected: usize,
+        actual: usize,
+    },
+    RecordLength {
+        expected: usize,
+        actual: usize,
+    },
+    RecordCount {
+        header: u32,
+        file: u32,
+    },
+    MissingEofMarker,
+    MissingMemoFile {
+        fields: Vec<String>,
+    },
+    UnparsableField {
+        field: String,
+        records: Vec<u32>,
+        error: String,
+    },
+    DanglingMemoPointer {
+        field: String,
+        records: Vec<u32>,
+    },
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::HeaderLength { expected, actual } => write!(
+                f,
+                "header length is {} but the field descriptors require at least {}",
+                actual, expected
+            ),
+            Issue::RecordLength { expected, actual } => write!(
+                f,
+                "record length is {} but the fields add up to {}",
+                actual, expected
+            ),
+            Issue::RecordCount { header, file } => write!(
+                f,
+                "header declares {} records but the file size allows {}",
+                header, file
+            ),
+            Issue::MissingEofMarker => write!(f, "end of file marker (0x1a) is missing"),
+            Issue::MissingMemoFile { fields } => {
+                write!(f, "memo fields {} have no memo file", fields.join(", "))
+            }
+            Issue::UnparsableField {
+                field,
+                records,
+                error,
+            } => write!(
+                f,
+                "field '{}' could not be parsed in {} record(s) {}: {}",
+                field,
+                records.len(),
+                format_records(records),
+                error
+            ),
+            Issue::DanglingMemoPointer { field, records } => write!(
+                f,
+                "field '{}' points outside the memo file in {} record(s) {}",
+                field,
+                records.len(),
+                format_records(records)
+            ),
+        }
+    }
+}
+
+fn format_records(records: &[u32]) -> String {
+    const MAX_LISTED: usize = 10;
+
+    let listed = records
+        .iter()
+        .take(MAX_LISTED)
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if records.len() > MAX_LISTED {
+        format!("({}, ...)", listed)
+    } else {
+        format!("({})", listed)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidationReport {
+    pub records_checked: u32,
+    pub issues: Vec<Issue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn from_reader<R: Read + Seek>(
+        mut table: R,
+        mut memo: Option<R>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let table_size = table.seek(SeekFrom::End(0))?;
+        table.seek(SeekFrom::Start(0))?;
+        let memo_size = match memo.as_mut() {
+            Some(memo) => {
+                let size = memo.seek(SeekFrom::End(0))?;
+                memo.seek(SeekFrom::Start(0))?;
+                Some(size)
+            }
+            None => None,
+        };
+
+        let mut reader = DbfReader::from_reader(table, memo)?;
+        let header = reader.header();
+        let version = header.version;
+        let header_length = header.header_length;
+        let descriptors_offset = header.field_descriptors_offset();
+        let record_length = header.record_length;
+        let record_count = header.record_count;
+        let fields = reader.fields().clone();
+        let null_flags_length = reader.null_flags().map(|n| n.length());
+        let memo_limits = reader
+            .memo_reader()
+            .map(|m| (m.next_block(), m.block_size() as u64));
+
+        let mut issues = Vec::new();
+
+        let descriptor_count = fields.len() + null_flags_length.is_some() as usize;
+        // padding after the terminator is valid (the Visual FoxPro backlink, dBase 7 field properties)
+        let expected_header_length =
+            descriptors_offset + descriptor_count * field_descriptor_length(version) + 1;
+        if header_length < expected_header_length {
+            issues.push(Issue::HeaderLength {
+                expected: expected_header_length,
+                actual: header_length,
+            });
+        }
+
+        let expected_record_length =
+            1 + fields.iter().map(|f| f.length).sum::<usize>() + null_flags_length.unwrap_or(0);
+        if record_length != expected_record_length {
+            issues.push(Issue::RecordLength {
+                expected: expected_record_length,
+                actual: record_length,
+            });
+        }
+
+        let data_size = table_size.saturating_sub(header_length as u64);
+        let file_records = match record_length {
+            0 => 0,
+            _ => (data_size / record_length as u64) as u32,
+        };
+        if file_records != record_count {
+            issues.push(Issue::RecordCount {
+                header: record_count,
+                file: file_records,
+            });
+        }
+
+        let eof_position = header_length as u64 + record_count as u64 * record_length as u64;
+        let has_eof_marker = eof_position < table_size && {
+            let table = reader.get_mut();
+            let position = table.stream_position()?;
+            let mut marker = [0u8; 1];
+            table.seek(SeekFrom::Start(eof_position))?;
+            table.read_exact(&mut marker)?;
+            table.seek(SeekFrom::Start(position))?;
+            marker[0] == EOF_MARKER
+        };
+        if !has_eof_marker {
+            issues.push(Issue::MissingEofMarker);
+        }
+
+        let memo_fields = fields
+            .iter()
+            .filter(|f| f.field_type.is_memo())
+            .map(|f| f.name.clone())
+            .collect::<Vec<_>>();
+        if memo_limits.is_none() && !memo_fields.is_empty() {
+            issues.push(Issue::MissingMemoFile {
+                fields: memo_fields,
+            });
+        }
+
+        let mut unparsable: BTreeMap<usize, (Vec<u32>, String)> = BTreeMap::new();
+        let mut dangling: BTreeMap<usize, Vec<u32>> = BTreeMap::new();
+        let mut records_checked = 0;
+
+        reader.set_include_deleted(true);
+        while let Some(mut record) = reader.next_raw_record()? {
+            records_checked += 1;
+            let record_number = record.record_number();
+
+            for i in 0..fields.len() {
+                if let (Some(block), Some((next_block, block_size))) =
+                    (record.memo_block(i), memo_limits)
+                {
+                    let out_of_file =
+                        memo_size.is_some_and(|size| block as u64 * block_size >= size);
+                    if block >= next_block || out_of_file {
+                        dangling.entry(i).or_default().push(record_number);
+                        continue;
+                    }
+                }
+
+                if let Err(e) = record.parse_field(i) {
+                    unparsable
+                        .entry(i)
+                        .or_insert_with(|| (Vec::new(), e.to_string()))
+                        .0
+                        .push(record_number);
+                }
+            }
+        }
+
+        for (i, (records, error)) in unparsable {
+            issues.push(Issue::UnparsableField {
+                field: fields[i].name.clone(),
+                records,
+                error,
+            });
+        }
+
+        for (i, records) in dangling {
+            issues.push(Issue::DanglingMemoPointer {
+                field: fields[i].name.clone(),
+                records,
+            });
+        }
+
+        Ok(Self {
+            records_checked,
+            issues,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::dbf::{DbfWriter, FieldInfo, FieldType, Version};
+    use crate::value::Value;
+
+    const HEADER_LENGTH: usize = 32 + 3 * 32 + 1;
+    const RECORD_LENGTH: usize = 1 + 5 + 3 + 10;
+
+    // NAME C(5), QTY N(3,0) and NOTE C(10), which `memo_field` turns into a memo field
+    fn table() -> Vec<u8> {
+        let fields = vec![
+            FieldInfo::with_type("NAME", FieldType::Character, 5, 0),
+            FieldInfo::with_type("QTY", FieldType::Numeric, 3, 0),
+            FieldInfo::with_type("NOTE", FieldType::Character, 10, 0),
+        ];
+        let mut writer =
+            DbfWriter::new(Cursor::new(Vec::new()), Version::DBase3, 0, fields).unwrap();
+        for (name, qty) in [("a", 1), ("b", 2)] {
+            let values = [Value::Str(name.to_owned()), Value::Int(qty), Value::Null];
+            writer.write_record(&values).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn memo_field(table: &mut [u8], blocks: [&[u8; 10]; 2]) {
+        table[32 + 2 * 32 + 11] = b'M';
+        for (i, block) in blocks.iter().enumerate() {
+            let start = HEADER_LENGTH + i * RECORD_LENGTH + 1 + 5 + 3;
+            table[start..start + 10].copy_from_slice(*block);
+        }
+    }
+
+    // a dBase III memo file with a single memo in block 1
+    fn memo() -> Vec<u8> {
+        let mut memo = vec![0u8; 512];
+        memo[..4].copy_from_slice(&2u32.to_le_bytes());
+        memo.extend_from_slice(b"note\x1a\x1a");
+        memo
+    }
+
+    fn issues(table: Vec<u8>, memo: Option<Vec<u8>>) -> Vec<Issue> {
+        let report = ValidationReport::from_reader(Cursor::new(table), memo.map(Cursor::new));
+        report.unwrap().issues
+    }
+
+    #[test]
+    fn valid_table() {
+        let mut table = table();
+        memo_field(&mut table, [b"         1", b"          "]);
+
+        let report = ValidationReport::from_reader(Cursor::new(table), Some(Cursor::new(memo())));
+
+        let report = report.unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.records_checked, 2);
+    }
+
+    #[test]
+    fn header_length() {
+        let mut table = table();
+        // the header ends before the descriptor terminator
+        table[8..10].copy_from_slice(&(HEADER_LENGTH as u16 - 1).to_le_bytes());
+
+        let issues = issues(table, None);
+
+        assert!(matches!(
+            issues[0],
+            Issue::HeaderLength { expected: HEADER_LENGTH, actual } if actual == HEADER_LENGTH - 1
+        ));
+    }
+
+    #[test]
+    fn record_length() {
+        let mut table = table();
+        table[10..12].copy_from_slice(&(RECORD_LENGTH as u16 + 1).to_le_bytes());
+
+        let issues = issues(table, None);
+
+        assert!(matches!(
+            issues[0],
+            Issue::RecordLength { expected: RECORD_LENGTH, actual } if actual == RECORD_LENGTH + 1
+        ));
+    }
+
+    #[test]
+    fn record_count() {
+        let mut table = table();
+        table[4..8].copy_from_slice(&3u32.to_le_bytes());
+
+        let issues = issues(table, None);
+
+        assert!(matches!(
+            issues[0],
+            Issue::RecordCount { header: 3, file: 2 }
+        ));
+    }
+
+    #[test]
+    fn missing_eof_marker() {
+        let mut table = table();
+        table.pop();
+
+        let issues = issues(table, None);
+
+        assert!(matches!(issues[..], [Issue::MissingEofMarker]));
+    }
+
+    #[test]
+    fn missing_memo_file() {
+        let mut table = table();
+        memo_field(&mut table, [b"         1", b"          "]);
+
+        let issues = issues(table, None);
+
+        assert!(matches!(&issues[..], [Issue::MissingMemoFile { fields }] if fields == &["NOTE"]));
+    }
+
+    #[test]
+    fn unparsable_field() {
+        let mut table = table();
+        let qty = HEADER_LENGTH + RECORD_LENGTH + 1 + 5;
+        table[qty..qty + 3].copy_from_slice(b"abc");
+
+        let issues = issues(table, None);
+
+        assert!(matches!(
+            &issues[..],
+            [Issue::UnparsableField { field, records, .. }] if field == "QTY" && records == &[2]
+        ));
+    }
+
+    #[test]
+    fn dangling_memo_pointer() {
+        let mut table = table();
+        memo_field(&mut table, [b"         1", b"         5"]);
+
+        let issues = issues(table, Some(memo()));
+
+        assert!(matches!(
+            &issues[..],
+            [Issue::DanglingMemoPointer { field, records }] if field == "NOTE" && records == &[2]
+        ));
+    }
+}
@@ -1,13 +1,14 @@
 use serde::{Serialize, Serializer};
 
 use crate::dbf::FieldValue;
-use crate::model::{Date, Timestamp};
+use crate::model::{Currency, Date, Timestamp};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Str(String),
-    Int(i32),
+    Int(i64),
     Float(f64),
+    Currency(Currency),
     Bool(bool),
     Date(Date),
     Timestamp(Timestamp),
@@ -23,8 +24,9 @@ impl Serialize for Value {
         match *self {
             Value::Bool(val) => serializer.serialize_bool(val),
             Value::Str(ref val) => serializer.serialize_str(val),
-            Value::Int(val) => serializer.serialize_i32(val),
+            Value::Int(val) => serializer.serialize_i64(val),
             Value::Float(val) => serializer.serialize_f64(val),
+            Value::Currency(ref val) => val.serialize(serializer),
             Value::Date(ref val) => serializer.serialize_str(&val.to_string()),
             Value::Timestamp(ref val) => serializer.serialize_str(&val.to_string()),
             Value::Bytes(ref val) => serializer.collect_seq(val),
@@ -39,11 +41,20 @@ impl From<FieldValue> for Value {
             FieldValue::Logical(val) => Value::Bool(val),
             FieldValue::Character(val) | FieldValue::Memo(val) => Value::Str(val),
             FieldValue::Integer(val) => Value::Int(val),
+            FieldValue::Currency(val) => Value::Currency(Currency::new(val)),
             FieldValue::Numeric(val) => Value::Float(val),
             FieldValue::Float(val) => Value::Float(val),
             FieldValue::Date(year, month, day) => Value::Date(Date::new(year, month, day)),
             FieldValue::Timestamp(year, month, day, hour, minute, second, millisecond) => {
-                Value::Timestamp(Timestamp::new(year, month, day, hour, minute, second, millisecond))
+                Value::Timestamp(Timestamp::new(
+                    year,
+                    month,
+                    day,
+                    hour,
+                    minute,
+                    second,
+                    millisecond,
+                ))
             }
             FieldValue::Binary(val) | FieldValue::General(val) => Value::Bytes(val),
             FieldValue::Null => Value::Null,
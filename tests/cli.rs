@@ -0,0 +1,219 @@
+#![cfg(feature = "cli")]
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use dbf_dextractor::{Date, DbfWriter, FieldInfo, FieldType, Value, Version};
+
+struct Directory(PathBuf);
+
+impl Directory {
+    fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("dbf-cli-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+        Directory(path)
+    }
+
+    fn join(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Drop for Directory {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn customers(path: &Path, records: &[(&str, i64, Option<Date>)]) {
+    let fields = vec![
+        FieldInfo::with_type("NAME", FieldType::Character, 10, 0),
+        FieldInfo::with_type("QTY", FieldType::Numeric, 5, 0),
+        FieldInfo::with_type("SINCE", FieldType::Date, 8, 0),
+    ];
+    let mut writer =
+        DbfWriter::new(File::create(path).unwrap(), Version::DBase3, 0, fields).unwrap();
+    for (name, qty, since) in records {
+        let since = since.clone().map_or(Value::Null, Value::Date);
+        writer
+            .write_record(&[Value::Str(name.to_string()), Value::Int(*qty), since])
+            .unwrap();
+    }
+    writer.finish().unwrap();
+}
+
+fn dbf<P: AsRef<Path>>(args: &[&str], table: P) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_dbf"))
+        .args(&args[..1])
+        .arg(table.as_ref())
+        .args(&args[1..])
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn info() {
+    let directory = Directory::new("info");
+    let table = directory.join("customers.dbf");
+    customers(&table, &[("Ana", 1, None), ("Bruno", 2, None)]);
+
+    let text = stdout(&dbf(&["info"], &table));
+    assert!(text.contains("Version:         DBase3\n"));
+    assert!(text.contains("Records:         2 (0 deleted)\n"));
+    assert!(text.contains("Fields:          3\n"));
+    assert!(text.contains("Memo file:       none\n"));
+
+    let json = stdout(&dbf(&["info", "--format", "json"], &table));
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json["record_count"], 2);
+    assert_eq!(json["table_size"], fs::metadata(&table).unwrap().len());
+    assert!(json["memo_file"].is_null());
+}
+
+#[test]
+fn schema() {
+    let directory = Directory::new("schema");
+    let table = directory.join("customers.dbf");
+    customers(&table, &[]);
+
+    let text = stdout(&dbf(&["schema"], &table));
+    let lines = text.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[0],
+        "Name   Type           Length  Decimals  Offset  Flags"
+    );
+    assert_eq!(lines[2], "NAME   Character (C)  10      0         1");
+    assert_eq!(lines[3], "QTY    Numeric (N)    5       0         11");
+
+    let json = stdout(&dbf(&["schema", "--format", "json"], &table));
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(json[2]["name"], "SINCE");
+    assert_eq!(json[2]["field_type"], "Date");
+}
+
+#[test]
+fn missing_table() {
+    let output = dbf(&["info"], "missing.dbf");
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("dbf: "));
+}
+
+#[test]
+fn export_csv() {
+    let directory = Directory::new("export-csv");
+    let table = directory.join("customers.dbf");
+    let since = Some(Date::new(2024, 2, 29));
+    customers(
+        &table,
+        &[("Ana", 1, since), ("Bruno", 2, None), ("Carla", 3, None)],
+    );
+
+    let csv = stdout(&dbf(&["export"], &table));
+    assert_eq!(
+        csv,
+        "NAME,QTY,SINCE\nAna,1,2024-02-29\nBruno,2,\nCarla,3,\n"
+    );
+
+    let args = [
+        "cat",
+        "--columns",
+        "qty,name",
+        "--offset",
+        "1",
+        "--limit",
+        "1",
+    ];
+    assert_eq!(stdout(&dbf(&args, &table)), "QTY,NAME\n2,Bruno\n");
+
+    let output_path = directory.join("customers.csv");
+    let output_arg = output_path.to_str().unwrap();
+    assert_eq!(stdout(&dbf(&["export", "-o", output_arg], &table)), "");
+    assert_eq!(fs::read_to_string(&output_path).unwrap(), csv);
+}
+
+#[test]
+fn export_json() {
+    let directory = Directory::new("export-json");
+    let table = directory.join("customers.dbf");
+    customers(
+        &table,
+        &[("Ana", 1, Some(Date::new(2024, 2, 29))), ("Bruno", 2, None)],
+    );
+
+    let json = stdout(&dbf(&["export", "--format", "json"], &table));
+    let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {"NAME": "Ana", "QTY": 1, "SINCE": "2024-02-29"},
+            {"NAME": "Bruno", "QTY": 2, "SINCE": null},
+        ])
+    );
+
+    let ndjson = stdout(&dbf(
+        &["export", "--format", "ndjson", "--columns", "name"],
+        &table,
+    ));
+    assert_eq!(ndjson, "{\"NAME\":\"Ana\"}\n{\"NAME\":\"Bruno\"}\n");
+}
+
+#[test]
+fn export_table() {
+    let directory = Directory::new("export-table");
+    let table = directory.join("customers.dbf");
+    customers(
+        &table,
+        &[
+            ("Ana", 1, Some(Date::new(2024, 2, 29))),
+            ("Bruno", 22, None),
+        ],
+    );
+
+    let text = stdout(&dbf(&["export", "--format", "table"], &table));
+
+    // columns are as wide as their fields
+    assert_eq!(
+        text,
+        "NAME        QTY    SINCE\n\
+         ----------  -----  ----------\n\
+         Ana         1      2024-02-29\n\
+         Bruno       22\n"
+    );
+}
+
+#[test]
+fn validate() {
+    let directory = Directory::new("validate");
+    let table = directory.join("customers.dbf");
+    customers(&table, &[("Ana", 1, None), ("Bruno", 2, None)]);
+
+    let text = stdout(&dbf(&["validate"], &table));
+    assert!(text.ends_with(": 2 records checked, 0 problem(s) found\n"));
+
+    // the end of file marker is cut off
+    let mut bytes = fs::read(&table).unwrap();
+    bytes.pop();
+    fs::write(&table, bytes).unwrap();
+
+    let output = dbf(&["validate"], &table);
+    assert_eq!(output.status.code(), Some(2));
+    let text = String::from_utf8(output.stdout).unwrap();
+    assert!(text.contains("1 problem(s) found\n  - end of file marker (0x1a) is missing\n"));
+
+    let output = dbf(&["validate", "--format", "json"], &table);
+    assert_eq!(output.status.code(), Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["records_checked"], 2);
+    assert_eq!(json["issues"][0]["kind"], "missing_eof_marker");
+}